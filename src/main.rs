@@ -7,39 +7,67 @@ use std::process::exit;
 use std::rc::Rc;
 use ziyy::Error;
 use ziyy_core::{
-    Document, Fragment, FragmentType, Indexer, Parser, Resolver, Result, Span, Splitter, WordParser,
+    ColorLevel, Document, Fragment, FragmentType, Icons, Indexer, Parser, RenderOptions, Resolver,
+    Result, Span, Splitter, Streamer, WordParser,
 };
 
 mod arg;
 
 fn parse_escapes_only(source: &str) -> Result<Rc<Document>> {
-    let parser = WordParser::new();
+    let mut parser = WordParser::new();
+    parser.set_icons(Icons::None);
     let span = Span::calculate(source);
-    let chunks = parser.parse(Fragment::new(FragmentType::Word, source.to_string(), span))?;
+    let chunks = parser.parse(Fragment::new(FragmentType::Word, source, span))?;
     // println!("{chunks:?}");
 
     let mut resolver = Resolver::new(true);
     resolver.resolve(chunks)
 }
 
-fn parse(source: &str) -> Result<Rc<Document>> {
+fn parse(source: &str, options: RenderOptions) -> Result<Rc<Document>> {
     let mut indexer = Indexer::new();
-    let source = indexer.index(source.to_string());
+    let source = indexer.index(source);
     let mut splitter = Splitter::new();
-    let frags = splitter.split(source)?;
+    let frags = splitter.split(&source)?;
 
     let parser = Parser::default();
     let chunks = parser.parse(frags)?;
 
     let mut resolver = Resolver::new(false);
+    resolver.set_options(&options);
     resolver.resolve(chunks)
 }
 
+/// Returns the options of the terminal, keeping every color unless styles
+/// are stripped, which writes links as text too. Shortcodes are expanded
+/// with `--icons` only.
+fn render_options(options: Options) -> RenderOptions {
+    let detected = RenderOptions {
+        icons: match options.icons {
+            true => Icons::detect(),
+            false => Icons::None,
+        },
+        ..RenderOptions::detect()
+    };
+    match options.strip {
+        true => RenderOptions {
+            level: ColorLevel::None,
+            hyperlinks: false,
+            controls: false,
+            ..detected
+        },
+        false => RenderOptions {
+            level: ColorLevel::TrueColor,
+            ..detected
+        },
+    }
+}
+
 fn parse_to_out(source: &str, out: &mut impl Write, options: Options) {
     let mut f = || {
         let output = match options.escape_only {
             true => parse_escapes_only(source),
-            false => parse(source),
+            false => parse(source, render_options(options)),
         }?;
 
         if options.strip {
@@ -64,7 +92,8 @@ fn parse_to_out(source: &str, out: &mut impl Write, options: Options) {
 
 fn usage() {
     let mut out = stdout();
-    let help = parse(&format!(include_str!("help.zy"), env!("CARGO_BIN_NAME"))).unwrap();
+    let source = format!(include_str!("help.zy"), env!("CARGO_BIN_NAME"));
+    let help = parse(&source, render_options(Options::default())).unwrap();
 
     if !out.is_terminal() {
         // help.root().strip_styles();
@@ -96,6 +125,7 @@ fn main() {
                 "ansi",
                 "cli",
                 "help",
+                "icons",
                 "no-newline",
                 "strip",
                 "version",
@@ -131,6 +161,8 @@ fn main() {
             | arg.is_long_switch_and(|s| s == "no-newline")
         {
             options.no_newline = true;
+        } else if arg.is_long_switch_and(|s| s == "icons") {
+            options.icons = true;
         } else if arg.is_long_switch_and(|s| s == "strip") {
             options.strip = true;
         } else if arg.is_long_switch_and(|s| s == "tree") {
@@ -141,15 +173,27 @@ fn main() {
     }
 
     if options.cli {
-        if params.is_empty() {
+        if params.is_empty() && !(options.escape_only || options.tree) {
+            let mut streamer = Streamer::new(render_options(options));
+            if let Err(err) = streamer.stream(stdin().lock(), &mut stdout) {
+                println!("{err}");
+                exit(1)
+            }
+            if !options.no_newline && !streamer.at_line_start() {
+                let _ = writeln!(stdout);
+            }
+        } else if params.is_empty() {
             let mut buf = String::new();
             let _ = stdin().read_to_string(&mut buf);
             parse_to_out(&buf, &mut out, options);
+            if !options.no_newline {
+                let _ = writeln!(out);
+            }
         } else {
             parse_to_out(&params.join(" "), &mut out, options);
-        }
-        if !options.no_newline {
-            let _ = writeln!(out);
+            if !options.no_newline {
+                let _ = writeln!(out);
+            }
         }
     } else {
         if params.is_empty() {
@@ -181,6 +225,7 @@ fn main() {
 struct Options {
     cli: bool,
     escape_only: bool,
+    icons: bool,
     no_newline: bool,
     strip: bool,
     tree: bool,
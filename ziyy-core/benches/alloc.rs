@@ -0,0 +1,72 @@
+//! Counts the heap allocations of every pipeline stage.
+//!
+//! Run with `cargo bench --bench alloc`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ziyy_core::{Indexer, Parser, Resolver, Splitter};
+
+struct Counter;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+/// Runs `f` and prints the allocations it made.
+fn count<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let (allocations, bytes) = (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        BYTES.load(Ordering::Relaxed),
+    );
+    let out = black_box(f());
+    println!(
+        "{name:<14} {:>8} allocations {:>10} bytes",
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        BYTES.load(Ordering::Relaxed) - bytes
+    );
+    out
+}
+
+fn main() {
+    let help = include_str!("../../src/help.zy").replace("{0}", "ziyy");
+    let log = "<let id='info' c='green' b /><info>INFO</info> <d>2024-01-01T00:00:00Z</d> \
+               request <i>GET /api/v1/items</i> took <c c='yellow'>{}</c>ms\n"
+        .repeat(100);
+
+    for (name, source) in [("help", help), ("log", log)] {
+        println!("{name}:");
+        let indexed = count("  index", || Indexer::new().index(&source));
+        let frags = count("  split", || Splitter::new().split(&indexed).unwrap());
+        let chunks = count("  parse", || Parser::new(false).parse(frags).unwrap());
+        let document = count("  resolve", || {
+            Resolver::new(false).resolve(chunks).unwrap()
+        });
+        count("  render", || {
+            let mut buf = String::new();
+            document.root().to_string(&mut buf);
+            buf
+        });
+    }
+}
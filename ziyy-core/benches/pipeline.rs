@@ -0,0 +1,80 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use ziyy_core::{Cache, ColorLevel, Indexer, Parser, Resolver, Splitter, try_style};
+
+fn help() -> String {
+    include_str!("../../src/help.zy").replace("{0}", "ziyy")
+}
+
+fn log_line() -> String {
+    "<let id='info' c='green' b /><info>INFO</info> <d>2024-01-01T00:00:00Z</d> \
+     request <i>GET /api/v1/items</i> took <c c='yellow'>{}</c>ms"
+        .to_string()
+}
+
+fn stages(c: &mut Criterion) {
+    for (name, source) in [("help", help()), ("log", log_line())] {
+        let mut group = c.benchmark_group(name);
+
+        group.bench_function("index", |b| {
+            let mut indexer = Indexer::new();
+            b.iter(|| black_box(indexer.index(black_box(&source)).len()))
+        });
+
+        let indexed = Indexer::new().index(&source).into_owned();
+        group.bench_function("split", |b| {
+            let mut splitter = Splitter::new();
+            b.iter(|| black_box(splitter.split(black_box(&indexed)).unwrap().len()))
+        });
+
+        let parser = Parser::new(false);
+        group.bench_function("parse", |b| {
+            b.iter_batched(
+                || Splitter::new().split(&indexed).unwrap(),
+                |frags| black_box(parser.parse(frags).unwrap()),
+                BatchSize::SmallInput,
+            )
+        });
+
+        let chunks = parser
+            .parse(Splitter::new().split(&indexed).unwrap())
+            .unwrap();
+        group.bench_function("resolve", |b| {
+            b.iter_batched(
+                || chunks.clone(),
+                |chunks| black_box(Resolver::new(false).resolve(chunks).unwrap()),
+                BatchSize::SmallInput,
+            )
+        });
+
+        let document = Resolver::new(false).resolve(chunks.clone()).unwrap();
+        group.bench_function("render", |b| {
+            b.iter(|| {
+                let mut buf = String::new();
+                document.root().to_string(&mut buf);
+                black_box(buf)
+            })
+        });
+
+        group.bench_function("try_style", |b| {
+            b.iter(|| black_box(try_style(black_box(&source)).unwrap()))
+        });
+
+        group.bench_function("cache/render", |b| {
+            let mut cache = Cache::new(1);
+            b.iter(|| {
+                black_box(
+                    cache
+                        .render(black_box(&source), ColorLevel::TrueColor)
+                        .unwrap(),
+                )
+            })
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, stages);
+criterion_main!(benches);
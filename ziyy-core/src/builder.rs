@@ -0,0 +1,208 @@
+use std::rc::Rc;
+
+use crate::{
+    Color, Document, RenderOptions, Resolver, Result,
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::{Tag, TagType},
+    },
+    splitter::is_whitespace,
+};
+
+/// Builds a document from code, without writing markup.
+///
+/// The builder produces the same chunks the [`Parser`](crate::Parser) would
+/// produce for the equivalent markup, so the resulting document is resolved
+/// and rendered exactly like a parsed one. Text is never interpreted as
+/// markup.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{Doc, try_style};
+///
+/// let out = Doc::new()
+///     .p(|p| p.bold("Usage:").text(" tool <FILE>"))
+///     .render()
+///     .unwrap();
+///
+/// assert_eq!(out, try_style(r"<p><b>Usage:</b> tool \<FILE\></p>").unwrap());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Doc {
+    chunks: Vec<Chunk>,
+}
+
+impl Doc {
+    /// Creates a new empty Doc.
+    pub fn new() -> Self {
+        Self {
+            chunks: Vec::with_capacity(16),
+        }
+    }
+
+    /// Appends text.
+    pub fn text(mut self, text: impl AsRef<str>) -> Self {
+        let text = text.as_ref();
+        let mut start = 0;
+        let mut ws = text.starts_with(is_whitespace);
+        for (i, c) in text.char_indices() {
+            if is_whitespace(c) != ws {
+                self.push_text(&text[start..i], ws);
+                start = i;
+                ws = !ws;
+            }
+        }
+        self.push_text(&text[start..], ws);
+        self
+    }
+
+    /// Appends an element with the open tag `tag`, whose content is built by `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` is a close tag.
+    pub fn element(mut self, tag: Tag, f: impl FnOnce(Doc) -> Doc) -> Self {
+        match tag.r#type {
+            TagType::Open => {
+                let close = tag.close();
+                self.push_tag(tag);
+                self.chunks.extend(f(Doc::new()).chunks);
+                self.push_tag(close);
+            }
+            TagType::SelfClose => self.push_tag(tag),
+            TagType::Close => panic!("cannot build an element from {tag:#}"),
+        }
+        self
+    }
+
+    /// Appends an element named `name`, whose content is built by `f`.
+    pub fn tag(self, name: &str, f: impl FnOnce(Doc) -> Doc) -> Self {
+        self.element(Tag::open(name), f)
+    }
+
+    /// Appends a paragraph.
+    pub fn p(self, f: impl FnOnce(Doc) -> Doc) -> Self {
+        self.tag("p", f)
+    }
+
+    /// Appends a block.
+    #[allow(clippy::should_implement_trait)]
+    pub fn div(self, f: impl FnOnce(Doc) -> Doc) -> Self {
+        self.tag("div", f)
+    }
+
+    /// Appends a line break.
+    pub fn br(self) -> Self {
+        self.element(Tag::with_name("br"), |doc| doc)
+    }
+
+    /// Appends a link to `href`.
+    pub fn a(self, href: &str, f: impl FnOnce(Doc) -> Doc) -> Self {
+        let mut tag = Tag::open("a");
+        tag.set_custom(href.to_string());
+        self.element(tag, f)
+    }
+
+    /// Appends bold text.
+    pub fn bold(self, text: impl AsRef<str>) -> Self {
+        self.tag("b", |doc| doc.text(text))
+    }
+
+    /// Appends dim text.
+    pub fn dim(self, text: impl AsRef<str>) -> Self {
+        self.tag("d", |doc| doc.text(text))
+    }
+
+    /// Appends italic text.
+    pub fn italic(self, text: impl AsRef<str>) -> Self {
+        self.tag("i", |doc| doc.text(text))
+    }
+
+    /// Appends underlined text.
+    pub fn underline(self, text: impl AsRef<str>) -> Self {
+        self.tag("u", |doc| doc.text(text))
+    }
+
+    /// Appends struck through text.
+    pub fn strike(self, text: impl AsRef<str>) -> Self {
+        self.tag("s", |doc| doc.text(text))
+    }
+
+    /// Appends text in the foreground color `color`.
+    pub fn fg(self, color: Color, text: impl AsRef<str>) -> Self {
+        let mut tag = Tag::open("c");
+        tag.set_fg_color(color);
+        self.element(tag, |doc| doc.text(text))
+    }
+
+    /// Appends text on the background color `color`.
+    pub fn bg(self, color: Color, text: impl AsRef<str>) -> Self {
+        let mut tag = Tag::open("x");
+        tag.set_bg_color(color);
+        self.element(tag, |doc| doc.text(text))
+    }
+
+    /// Returns the chunks of this Doc.
+    pub fn into_chunks(self) -> Vec<Chunk> {
+        self.chunks
+    }
+
+    /// Returns the unresolved document of this Doc, see [`Resolver::tree`].
+    pub fn tree(self) -> Rc<Document> {
+        Resolver::tree(self.chunks)
+    }
+
+    /// Resolves this Doc into a [`Document`].
+    pub fn build(self) -> Result<Rc<Document>> {
+        self.build_for(RenderOptions::default())
+    }
+
+    /// Resolves this Doc into a [`Document`] rendered with `options`.
+    pub fn build_for(self, options: impl Into<RenderOptions>) -> Result<Rc<Document>> {
+        options.into().resolve(self.chunks)
+    }
+
+    /// Renders this Doc.
+    pub fn render(self) -> Result<String> {
+        self.render_for(RenderOptions::default())
+    }
+
+    /// Renders this Doc with `options`, such as the colors of a
+    /// [`ColorLevel`](crate::ColorLevel).
+    pub fn render_for(self, options: impl Into<RenderOptions>) -> Result<String> {
+        let doc = self.build_for(options)?;
+        let mut buf = String::new();
+        doc.root().to_string(&mut buf);
+        Ok(buf)
+    }
+
+    fn push_tag(&mut self, tag: Tag) {
+        self.chunks.push(Chunk {
+            data: ChunkData::Tag(tag),
+            span: Span::inserted(),
+        });
+    }
+
+    fn push_text(&mut self, text: &str, ws: bool) {
+        if text.is_empty() {
+            return;
+        }
+
+        let data = if ws {
+            ChunkData::WhiteSpace(text.to_string())
+        } else if text.contains('\\') {
+            // words are unescaped by the resolver
+            ChunkData::Word(text.replace('\\', "\\\\"))
+        } else {
+            ChunkData::Word(text.to_string())
+        };
+
+        self.chunks.push(Chunk {
+            data,
+            span: Span::inserted(),
+        });
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, sync::LazyLock};
 
-use crate::parser::ansi::{Ansi, AnsiOptions, Effect, DuoEffect};
+use crate::parser::ansi::{Ansi, AnsiOptions, DuoEffect, Effect};
 
 pub static BUILTIN_STYLES: LazyLock<HashMap<&str, Ansi>> = LazyLock::new(|| {
     [
@@ -72,7 +72,8 @@ pub static BUILTIN_STYLES: LazyLock<HashMap<&str, Ansi>> = LazyLock::new(|| {
 });
 
 pub static BUILTIN_TAGS: &[&str] = &[
-    "a", "b", "blink", "br", "d", "del", "dim", "div", "em", "h", "i", "input", "ins", "k", "o",
-    "over", "p", "r", "s", "script", "span", "strong", "style", "table", "td", "th", "tr", "u",
-    "uu", "ziyy",
+    "a", "b", "bar", "bell", "blink", "box", "br", "code", "cursor", "d", "del", "diff", "dim",
+    "div", "em", "erase", "h", "hr", "i", "img", "input", "ins", "k", "li", "o", "ol", "over", "p",
+    "pre", "progress", "r", "s", "screen", "script", "space", "span", "sparkline", "spinner",
+    "strong", "style", "table", "td", "th", "title", "tr", "u", "ul", "uu", "ziyy",
 ];
@@ -0,0 +1,134 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    hash::{DefaultHasher, Hash, Hasher},
+    rc::Rc,
+};
+
+use crate::{Document, FrozenDocument, Indexer, RenderOptions, Result};
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::new(Cache::new(Cache::DEFAULT_CAPACITY));
+}
+
+type Key = (u64, RenderOptions);
+
+/// A cache of resolved documents keyed by the hash of their source.
+///
+/// Rendering the same template twice through a cache only parses it once,
+/// where [`try_style`](crate::try_style) parses it on every call.
+/// [`with_thread_local`](Cache::with_thread_local) shares one cache per
+/// thread. Documents are kept frozen, so every caller gets a copy of its own
+/// to change.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{Cache, ColorLevel};
+///
+/// let mut cache = Cache::new(16);
+/// let a = cache.resolve("<b>bold</b>", ColorLevel::TrueColor).unwrap();
+/// a.root().first_child().unwrap().remove().unwrap();
+///
+/// let b = cache.resolve("<b>bold</b>", ColorLevel::TrueColor).unwrap();
+/// assert_eq!(b.freeze().render(), "\x1b[1mbold\x1b[22m");
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub struct Cache {
+    documents: HashMap<Key, (Box<str>, FrozenDocument)>,
+    /// Keys of the cached documents, the oldest first.
+    order: VecDeque<Key>,
+    capacity: usize,
+}
+
+impl Cache {
+    /// Number of documents kept by the cache of every thread.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Creates a new Cache holding at most `capacity` documents.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            documents: HashMap::with_capacity(capacity.min(Self::DEFAULT_CAPACITY)),
+            order: VecDeque::with_capacity(capacity.min(Self::DEFAULT_CAPACITY)),
+            capacity,
+        }
+    }
+
+    /// Returns the document resolved from `source` with `options`, such as
+    /// the colors of a [`ColorLevel`](crate::ColorLevel), resolving it only
+    /// if it is not cached yet.
+    ///
+    /// When the cache is full, the document cached first is dropped.
+    pub fn resolve(
+        &mut self,
+        source: &str,
+        options: impl Into<RenderOptions>,
+    ) -> Result<Rc<Document>> {
+        self.with_frozen(source, options.into(), FrozenDocument::thaw)
+    }
+
+    /// Returns the text rendered from `source` with `options`, like
+    /// [`resolve`](Cache::resolve) without copying the document.
+    pub fn render(&mut self, source: &str, options: impl Into<RenderOptions>) -> Result<String> {
+        self.with_frozen(source, options.into(), FrozenDocument::render)
+    }
+
+    fn with_frozen<R>(
+        &mut self,
+        source: &str,
+        options: RenderOptions,
+        f: impl FnOnce(&FrozenDocument) -> R,
+    ) -> Result<R> {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        let key = (hasher.finish(), options);
+
+        if let Some((cached, document)) = self.documents.get(&key)
+            && **cached == *source
+        {
+            return Ok(f(document));
+        }
+
+        let document = options.parse(&Indexer::new().index(source))?.freeze();
+        let output = f(&document);
+        if self.capacity == 0 {
+            return Ok(output);
+        }
+        if self.documents.remove(&key).is_some() {
+            // another source with the same hash
+            self.order.retain(|old| *old != key);
+        }
+        while self.documents.len() >= self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.documents.remove(&oldest);
+        }
+        self.documents.insert(key, (source.into(), document));
+        self.order.push_back(key);
+
+        Ok(output)
+    }
+
+    /// Returns the number of cached documents.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Returns true if no documents are cached.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Removes all cached documents.
+    pub fn clear(&mut self) {
+        self.documents.clear();
+        self.order.clear();
+    }
+
+    /// Runs `f` with the cache of the current thread.
+    pub fn with_thread_local<R>(f: impl FnOnce(&mut Cache) -> R) -> R {
+        CACHE.with_borrow_mut(f)
+    }
+}
@@ -1,57 +1,67 @@
-use std::{
-    fmt::Display,
-    ops::{AddAssign, SubAssign},
-};
+use std::{fmt::Display, ops::AddAssign};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 /// A position in source code.
 pub struct Position {
     /// Line in source code.
     pub line: i32,
     /// Column in source code.
     pub column: i32,
+    /// Byte offset in source code.
+    pub offset: usize,
 }
 
 impl Default for Position {
     fn default() -> Self {
-        Self { line: 1, column: 1 }
+        Self {
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
     }
 }
 
 impl Position {
     /// Creates a new Position.
     pub fn new(line: i32, column: i32) -> Self {
-        Self { line, column }
+        Self {
+            line,
+            column,
+            offset: 0,
+        }
     }
-}
 
-impl AddAssign<(i32, i32)> for Position {
-    fn add_assign(&mut self, rhs: (i32, i32)) {
-        let (line, column) = rhs;
-        self.line += line;
-        if line > 0 {
-            self.column = 1;
-        } else {
-            self.column += column;
+    /// Creates the Position of byte `offset` in `source`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is not on a char boundary of `source`.
+    pub fn locate(source: &str, offset: usize) -> Self {
+        let mut pos = Position::default();
+        for c in source[..offset].chars() {
+            pos += c;
         }
+        pos
     }
-}
 
-impl SubAssign<(i32, i32)> for Position {
-    fn sub_assign(&mut self, rhs: (i32, i32)) {
-        let (line, column) = rhs;
-        self.line -= line;
-        self.column -= column;
+    /// Moves back over `c`, which must not be a newline.
+    pub(crate) fn retreat(&mut self, c: char) {
+        debug_assert_ne!(c, '\n');
+        self.offset = self.offset.saturating_sub(c.len_utf8());
+        self.column -= 1;
     }
 }
 
-impl PartialOrd for Position {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.line.partial_cmp(&other.line) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
+impl AddAssign<char> for Position {
+    /// Moves past `c`.
+    fn add_assign(&mut self, c: char) {
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
         }
-        self.column.partial_cmp(&other.column)
     }
 }
 
@@ -1,6 +1,6 @@
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Sub},
+    ops::{AddAssign, Range},
 };
 
 use super::Position;
@@ -28,8 +28,20 @@ impl Span {
 
     pub(crate) fn unquote(&self) -> Self {
         let mut span = *self;
-        span.start += (0, 1);
-        span.end -= (0, 1);
+        span.start += '"';
+        if span.start < span.end {
+            span.end.retreat('"');
+        }
+        span
+    }
+
+    /// Moves the start of this span back over `text`, which was inserted in
+    /// front of it on the same line.
+    pub(crate) fn before(&self, text: &str) -> Self {
+        let mut span = *self;
+        for c in text.chars().rev() {
+            span.start.retreat(c);
+        }
         span
     }
 
@@ -42,33 +54,31 @@ impl Span {
     /// Calculate Span from source.
     pub fn calculate(source: &str) -> Self {
         let start = Position::default();
-
-        let mut end = start;
-        for c in source.chars() {
-            if c == '\n' {
-                end.line += 1;
-                end.column = 1;
-            } else {
-                end.column += 1;
-            }
-        }
+        let end = Position::locate(source, source.len());
 
         Self { start, end }
     }
-}
 
-impl Add<(i32, i32)> for Span {
-    type Output = Span;
+    /// Returns the start of this span.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end of this span.
+    pub fn end(&self) -> Position {
+        self.end
+    }
 
-    fn add(mut self, rhs: (i32, i32)) -> Self::Output {
-        self.end += rhs;
-        self
+    /// Returns the byte range of this span in its source.
+    pub fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
     }
 }
 
-impl AddAssign<(i32, i32)> for Span {
-    fn add_assign(&mut self, rhs: (i32, i32)) {
-        self.end += rhs;
+impl AddAssign<char> for Span {
+    /// Extends the end of this span past `c`.
+    fn add_assign(&mut self, c: char) {
+        self.end += c;
     }
 }
 
@@ -78,15 +88,6 @@ impl AddAssign for Span {
     }
 }
 
-impl Sub<(i32, i32)> for Span {
-    type Output = Span;
-
-    fn sub(mut self, rhs: (i32, i32)) -> Self::Output {
-        self.start -= rhs;
-        self
-    }
-}
-
 impl Display for Span {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
@@ -20,6 +20,12 @@ pub enum ErrorType {
     UnexpectedEof,
     /// Indicates an unterminated string literal.
     UnterminatedString,
+    /// Indicates a node which was removed from its document.
+    StaleNode,
+    /// Indicates a change which would leave a document malformed.
+    InvalidOperation,
+    /// Indicates an invalid selector.
+    InvalidSelector,
 }
 
 /// Represents an error with additional context such as its type, message, and location.
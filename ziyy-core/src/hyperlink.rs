@@ -0,0 +1,132 @@
+//! Hyperlink targets written with OSC 8.
+
+use std::{
+    borrow::Cow,
+    env,
+    fmt::Write,
+    fs, io,
+    path::{Path, absolute},
+};
+
+/// Returns the `file://` URL of `path`, made absolute against the current
+/// directory, with the name of this host so that terminals can tell local
+/// files from remote ones.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::file_url;
+///
+/// let url = file_url("/tmp/a b.txt").unwrap();
+/// assert!(url.starts_with("file://"));
+/// assert!(url.ends_with("/tmp/a%20b.txt"));
+/// ```
+pub fn file_url(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = absolute(path)?;
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = format!("file://{}", hostname().unwrap_or_default());
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => {
+                let _ = write!(url, "%{byte:02X}");
+            }
+        }
+    }
+    Ok(url)
+}
+
+/// Percent-encodes the bytes of `href` which may not appear in an OSC 8
+/// sequence, such as spaces and control characters.
+pub(crate) fn escape_href(href: &str) -> Cow<'_, str> {
+    if href.bytes().all(|byte| (0x21..=0x7e).contains(&byte)) {
+        return Cow::Borrowed(href);
+    }
+    let mut escaped = String::with_capacity(href.len() + 8);
+    for byte in href.bytes() {
+        match byte {
+            0x21..=0x7e => escaped.push(byte as char),
+            _ => {
+                let _ = write!(escaped, "%{byte:02X}");
+            }
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Schemes a link may point to, and whether their URLs need a host.
+const SCHEMES: [(&str, bool); 8] = [
+    ("file", false),
+    ("ftp", true),
+    ("git", true),
+    ("http", true),
+    ("https", true),
+    ("mailto", false),
+    ("sftp", true),
+    ("ssh", true),
+];
+
+/// Returns whether `href` is a URL with a known scheme, and a host if its
+/// scheme needs one, which terminals can open. Other targets, such as
+/// `javascript:` ones or plain words, are not written as hyperlinks.
+pub(crate) fn is_valid_href(href: &str) -> bool {
+    let Some((scheme, rest)) = href.split_once(':') else {
+        return false;
+    };
+    let Some(&(_, needs_host)) = SCHEMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(scheme))
+    else {
+        return false;
+    };
+    if scheme.eq_ignore_ascii_case("mailto") {
+        return rest.contains('@') && !rest.starts_with('@');
+    }
+
+    let Some(rest) = rest.strip_prefix("//") else {
+        // only hosts are left out of file URLs, as in `file:/tmp`
+        return !needs_host && rest.starts_with('/');
+    };
+    let authority = &rest[..rest.find(['/', '?', '#']).unwrap_or(rest.len())];
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = host
+        .rsplit_once(':')
+        .filter(|(_, port)| port.bytes().all(|c| c.is_ascii_digit()))
+        .map_or(host, |(host, _)| host);
+    let host_ok = host.bytes().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, b'-' | b'.' | b'_' | b'[' | b']' | b':' | b'%')
+    });
+    match needs_host {
+        true => !host.is_empty() && host_ok,
+        false => host_ok && rest[authority.len()..].starts_with('/'),
+    }
+}
+
+/// Drops the characters of a link `id` which would end its parameter.
+pub(crate) fn escape_id(id: &str) -> String {
+    id.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, ':' | ';' | '='))
+        .collect()
+}
+
+/// Returns the name of this host, from the kernel on Linux or `HOSTNAME`.
+#[cfg(unix)]
+fn hostname() -> Option<String> {
+    let name = fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| env::var("HOSTNAME"))
+        .ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+#[cfg(not(unix))]
+fn hostname() -> Option<String> {
+    env::var("COMPUTERNAME").ok()
+}
@@ -0,0 +1,271 @@
+//! Images shown with terminal graphics protocols or as block characters.
+
+use std::{fmt::Write, fs::File, io::Read};
+
+mod inflate;
+mod png;
+
+pub(crate) use png::is_png;
+
+/// Most pixels an image may have, which keeps a crafted header from asking
+/// for more memory than the image could ever be shown with.
+pub(crate) const MAX_PIXELS: usize = 1 << 24;
+
+/// Largest image file read, in bytes.
+pub(crate) const MAX_FILE: u64 = 16 << 20;
+
+/// Reads the file at `path`, unless it is larger than [`MAX_FILE`].
+pub(crate) fn read(path: &str) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(MAX_FILE + 1)
+        .read_to_end(&mut data)
+        .ok()?;
+    (data.len() as u64 <= MAX_FILE).then_some(data)
+}
+
+/// Returns whether an image of `width` by `height` pixels is not empty and
+/// has at most [`MAX_PIXELS`].
+pub(crate) fn fits(width: usize, height: usize) -> bool {
+    width > 0 && height > 0 && width.checked_mul(height).is_some_and(|n| n <= MAX_PIXELS)
+}
+
+/// An image with 8-bit RGBA pixels, row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Image {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// Decodes a PNG or binary PPM image.
+    pub(crate) fn decode(data: &[u8]) -> Option<Self> {
+        match png::is_png(data) {
+            true => png::decode(data),
+            false => ppm(data),
+        }
+    }
+
+    /// Returns the image scaled to `width` by `height` pixels, averaging the
+    /// pixels every new pixel covers.
+    pub(crate) fn resize(&self, width: usize, height: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let (top, bottom) = span(y, height, self.height);
+            for x in 0..width {
+                let (left, right) = span(x, width, self.width);
+                let mut sum = [0usize; 4];
+                for row in top..bottom {
+                    for pixel in &self.pixels[row * self.width + left..row * self.width + right] {
+                        for c in 0..4 {
+                            sum[c] += pixel[c] as usize;
+                        }
+                    }
+                }
+                let n = (bottom - top) * (right - left);
+                pixels.push(sum.map(|sum| (sum / n) as u8));
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        self.pixels[y * self.width + x]
+    }
+}
+
+/// Returns the range of the `size` old pixels which new pixel `i` out of
+/// `new` covers, which is never empty.
+fn span(i: usize, new: usize, size: usize) -> (usize, usize) {
+    let start = i * size / new;
+    let end = ((i + 1) * size / new).max(start + 1).min(size);
+    (start.min(size - 1), end)
+}
+
+/// Decodes a binary PPM image, as written by most image tools with `P6`.
+fn ppm(data: &[u8]) -> Option<Image> {
+    let mut fields = Vec::with_capacity(4);
+    let mut i = 0;
+    while fields.len() < 4 {
+        match data.get(i)? {
+            b'#' => {
+                while *data.get(i)? != b'\n' {
+                    i += 1;
+                }
+            }
+            c if c.is_ascii_whitespace() => i += 1,
+            _ => {
+                let start = i;
+                while data.get(i).is_some_and(|c| !c.is_ascii_whitespace()) {
+                    i += 1;
+                }
+                fields.push(std::str::from_utf8(&data[start..i]).ok()?);
+            }
+        }
+    }
+    if fields[0] != "P6" {
+        return None;
+    }
+    let [width, height, max] = [1, 2, 3].map(|i| fields[i].parse::<usize>().unwrap_or(0));
+    if !fits(width, height) || !(1..256).contains(&max) {
+        return None;
+    }
+    let body = data.get(i + 1..i + 1 + width * height * 3)?;
+    let pixels = body
+        .chunks(3)
+        .map(|rgb| {
+            let [r, g, b] = [0, 1, 2].map(|c| (rgb[c] as usize * 255 / max) as u8);
+            [r, g, b, 255]
+        })
+        .collect();
+    Some(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Returns the escape sequence which shows `image`, decoded from the file
+/// `data`, in `columns` by `rows` cells with the kitty graphics protocol. PNG
+/// files are sent as they are, other images as their pixels.
+pub(crate) fn kitty(data: &[u8], image: &Image, columns: usize, rows: usize) -> String {
+    let (header, payload) = match is_png(data) {
+        true => (format!("a=T,f=100,c={columns},r={rows}"), base64(data)),
+        false => {
+            let pixels: Vec<u8> = image.pixels.iter().flatten().copied().collect();
+            (
+                format!(
+                    "a=T,f=32,s={},v={},c={columns},r={rows}",
+                    image.width, image.height
+                ),
+                base64(&pixels),
+            )
+        }
+    };
+
+    // the payload is sent in chunks of at most 4096 bytes
+    let mut out = String::new();
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(4096)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        match i {
+            0 => out.push_str(&format!("\x1b_G{header},m={more};{chunk}\x1b\\")),
+            _ => out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\")),
+        }
+    }
+    out
+}
+
+/// Returns the escape sequence which shows the image file `data` `columns`
+/// cells wide, and `rows` high if given, with the inline images protocol of
+/// iTerm2.
+pub(crate) fn iterm2(data: &[u8], columns: usize, rows: Option<usize>) -> String {
+    let height = rows
+        .map(|rows| format!("height={rows};"))
+        .unwrap_or_default();
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={columns};{height}preserveAspectRatio=1:{}\x07",
+        data.len(),
+        base64(data)
+    )
+}
+
+/// Returns the sixel sequence which draws `image`, with its colors reduced
+/// to a cube of 6 levels per channel. Transparent pixels are left out.
+pub(crate) fn sixel(image: &Image) -> String {
+    let level = |c: u8| (c as usize * 5 + 127) / 255;
+    let index = |[r, g, b, _]: [u8; 4]| level(r) * 36 + level(g) * 6 + level(b);
+
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", image.width, image.height);
+    for i in 0..216 {
+        let [r, g, b] = [i / 36, i / 6 % 6, i % 6].map(|level| level * 100 / 5);
+        let _ = write!(out, "#{i};2;{r};{g};{b}");
+    }
+
+    for band in (0..image.height).step_by(6) {
+        let rows = (image.height - band).min(6);
+        let mut colors: Vec<usize> = Vec::new();
+        for y in band..band + rows {
+            for x in 0..image.width {
+                let pixel = image.pixel(x, y);
+                if pixel[3] >= 128 && !colors.contains(&index(pixel)) {
+                    colors.push(index(pixel));
+                }
+            }
+        }
+
+        for (n, &color) in colors.iter().enumerate() {
+            if n > 0 {
+                out.push('$');
+            }
+            let _ = write!(out, "#{color}");
+            let sixels = (0..image.width).map(|x| {
+                let mut bits = 0;
+                for row in 0..rows {
+                    let pixel = image.pixel(x, band + row);
+                    if pixel[3] >= 128 && index(pixel) == color {
+                        bits |= 1 << row;
+                    }
+                }
+                (63 + bits) as u8 as char
+            });
+            run_length(&mut out, sixels);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Writes `sixels`, repeating runs of more than three with `!`.
+fn run_length(out: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |out: &mut String, (c, n): (char, usize)| match n {
+        1..=3 => out.extend((0..n).map(|_| c)),
+        _ => {
+            let _ = write!(out, "!{n}{c}");
+        }
+    };
+    for c in sixels {
+        run = match run {
+            Some((last, n)) if last == c => Some((c, n + 1)),
+            Some(last) => {
+                flush(out, last);
+                Some((c, 1))
+            }
+            None => Some((c, 1)),
+        };
+    }
+    if let Some(last) = run {
+        flush(out, last);
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
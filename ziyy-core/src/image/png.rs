@@ -0,0 +1,166 @@
+//! Decoding of PNG images that are not interlaced.
+
+use super::{Image, fits, inflate::zlib};
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Returns whether `data` starts like a PNG image.
+pub(crate) fn is_png(data: &[u8]) -> bool {
+    data.starts_with(SIGNATURE)
+}
+
+/// Decodes the PNG image `data` to 8-bit RGBA pixels.
+pub(crate) fn decode(data: &[u8]) -> Option<Image> {
+    let mut rest = data.strip_prefix(SIGNATURE)?;
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut alphas: &[u8] = &[];
+    let mut compressed = Vec::new();
+    while rest.len() >= 12 {
+        let len = u32::from_be_bytes(rest[..4].try_into().ok()?) as usize;
+        let kind = &rest[4..8];
+        let body = rest.get(8..8 + len)?;
+        match kind {
+            b"IHDR" => header = Some(body),
+            b"PLTE" => palette = body,
+            b"tRNS" => alphas = body,
+            b"IDAT" => compressed.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        rest = rest.get(12 + len..)?;
+    }
+
+    let header = header?;
+    if header.len() < 13 || header[12] != 0 {
+        return None;
+    }
+    let width = u32::from_be_bytes(header[..4].try_into().ok()?) as usize;
+    let height = u32::from_be_bytes(header[4..8].try_into().ok()?) as usize;
+    let depth = header[8] as usize;
+    let channels = match header[9] {
+        0 | 3 => 1,
+        2 => 3,
+        4 => 2,
+        6 => 4,
+        _ => return None,
+    };
+    if !matches!(depth, 1 | 2 | 4 | 8 | 16) || !fits(width, height) {
+        return None;
+    }
+
+    let bits = depth * channels;
+    let stride = width.checked_mul(bits)?.div_ceil(8);
+    // bytes of a whole pixel, which filters look back by
+    let step = bits.div_ceil(8);
+    let size = (stride + 1).checked_mul(height)?;
+    let raw = zlib(&compressed, size)?;
+    if raw.len() < size {
+        return None;
+    }
+    let mut lines = vec![0u8; stride * height];
+    for y in 0..height {
+        let line = raw.get(y * (stride + 1)..(y + 1) * (stride + 1))?;
+        let (filter, line) = (line[0], &line[1..]);
+        let (done, current) = lines.split_at_mut(y * stride);
+        let current = &mut current[..stride];
+        let above = match y {
+            0 => None,
+            _ => Some(&done[(y - 1) * stride..]),
+        };
+        for x in 0..stride {
+            let a = if x >= step { current[x - step] } else { 0 };
+            let b = above.map_or(0, |above| above[x]);
+            let c = match above {
+                Some(above) if x >= step => above[x - step],
+                _ => 0,
+            };
+            current[x] = line[x].wrapping_add(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return None,
+            });
+        }
+    }
+
+    // reads the sample `i` of a line, scaled to 8 bits
+    let sample = |line: &[u8], i: usize| -> u16 {
+        match depth {
+            16 => u16::from_be_bytes([line[i * 2], line[i * 2 + 1]]),
+            8 => line[i] as u16,
+            _ => {
+                let per_byte = 8 / depth;
+                let shift = 8 - depth * (i % per_byte + 1);
+                ((line[i / per_byte] >> shift) & ((1 << depth) - 1) as u8) as u16
+            }
+        }
+    };
+    let scale = |value: u16| -> u8 {
+        match depth {
+            16 => (value >> 8) as u8,
+            8 => value as u8,
+            _ => (value as u32 * 255 / ((1 << depth) - 1)) as u8,
+        }
+    };
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let line = &lines[y * stride..(y + 1) * stride];
+        for x in 0..width {
+            let pixel = match header[9] {
+                0 => {
+                    let value = sample(line, x);
+                    let transparent =
+                        alphas.len() >= 2 && u16::from_be_bytes([alphas[0], alphas[1]]) == value;
+                    let gray = scale(value);
+                    [gray, gray, gray, if transparent { 0 } else { 255 }]
+                }
+                2 => {
+                    let [r, g, b] = [0, 1, 2].map(|c| scale(sample(line, x * 3 + c)));
+                    [r, g, b, 255]
+                }
+                3 => {
+                    let i = sample(line, x) as usize;
+                    let rgb = palette.get(i * 3..i * 3 + 3)?;
+                    [
+                        rgb[0],
+                        rgb[1],
+                        rgb[2],
+                        alphas.get(i).copied().unwrap_or(255),
+                    ]
+                }
+                4 => {
+                    let gray = scale(sample(line, x * 2));
+                    [gray, gray, gray, scale(sample(line, x * 2 + 1))]
+                }
+                _ => [0, 1, 2, 3].map(|c| scale(sample(line, x * 4 + c))),
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    Some(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
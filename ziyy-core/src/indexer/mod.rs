@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::fmt::Display;
 use std::mem::take;
 
 // mod scanner;
@@ -6,10 +8,7 @@ use std::mem::take;
 /// The Indexer adds indices to empty placeholders
 #[doc(hidden)]
 pub struct Indexer {
-    source: String,
-    start: usize,
-    current: usize,
-    parts: Vec<String>,
+    parts: String,
 }
 
 impl Default for Indexer {
@@ -21,66 +20,123 @@ impl Default for Indexer {
 impl Indexer {
     pub fn new() -> Self {
         Self {
-            source: String::new(),
-            start: 0,
-            current: 0,
-            parts: Vec::new(),
+            parts: String::new(),
         }
     }
 
-    pub fn index(&mut self, source: String) -> String {
-        self.source = source;
-        self.parts = Vec::with_capacity(self.source.len() / 2);
+    /// Numbers the empty placeholders in `source`, borrowing it when it has none.
+    pub fn index<'a>(&mut self, source: &'a str) -> Cow<'a, str> {
+        let bytes = source.as_bytes();
+        let peek = |i: usize| bytes.get(i).copied().unwrap_or(b'\0');
 
+        let mut start = 0;
+        let mut current = 0;
         let mut index = 0;
-        while !self.is_at_end() {
-            self.start = self.current;
+        self.parts.clear();
 
-            if self.peek(0) == b'{' && self.peek(1) == b'{' {
-                self.advance(2);
-                self.add_part();
-            } else if self.peek(0) == b'{' && self.peek(1) == b'}' && self.peek(2) != b'}' {
-                self.advance(2);
-                self.parts.push(format!("{{{index}}}"));
+        while current < bytes.len() {
+            if peek(current) == b'{' && peek(current + 1) == b'{' {
+                current += 2;
+            } else if peek(current) == b'{'
+                && peek(current + 1) == b'}'
+                && peek(current + 2) != b'}'
+            {
+                self.parts.push_str(&source[start..current]);
+                self.parts.push_str(&format!("{{{index}}}"));
                 index += 1;
+                current += 2;
+                start = current;
             } else {
-                loop {
-                    self.advance(1);
-
-                    if self.is_at_end() {
-                        break;
-                    }
-                    if self.peek(0) == b'{' && self.peek(1) == b'{' {
-                        break;
-                    }
-                    if self.peek(0) == b'{' && self.peek(1) == b'}' && self.peek(2) != b'}' {
-                        break;
-                    }
-                }
-                self.add_part();
+                current += 1;
             }
         }
-        take(&mut self.parts).join("")
-    }
 
-    fn peek(&self, n: usize) -> u8 {
-        if self.current + n >= self.source.len() {
-            b'\0'
-        } else {
-            self.source.as_bytes()[self.current + n]
+        if index == 0 {
+            return Cow::Borrowed(source);
         }
-    }
 
-    fn is_at_end(&self) -> bool {
-        self.current >= self.source.len()
+        self.parts.push_str(&source[start..]);
+        Cow::Owned(take(&mut self.parts))
     }
 
-    fn advance(&mut self, n: usize) {
-        self.current += n;
+    /// Replaces the placeholders in `source` with `args`, numbering the empty
+    /// ones first, and unescapes `{{` and `}}`.
+    ///
+    /// Arguments are text, never markup: in text, `<`, `>` and `\` are
+    /// escaped, and in an attribute value the quote around it, which cannot
+    /// be escaped, is left out. Anywhere else in a tag an argument is quoted
+    /// like a value, so it can neither add attributes nor end the tag.
+    /// Placeholders without an argument are kept as they are.
+    pub fn fill(&mut self, source: &str, args: &[&dyn Display]) -> String {
+        let source = self.index(source);
+        let mut filled = String::with_capacity(source.len());
+        let mut rest = &*source;
+        // whether `rest` is inside a tag, and the quote of its value if any
+        let mut tag = false;
+        let mut quote = None;
+        while let Some(i) = rest.find(['{', '}', '<', '>', '\'', '"', '\\']) {
+            filled.push_str(&rest[..i]);
+            rest = &rest[i..];
+            let c = rest.chars().next().unwrap();
+            match c {
+                '\\' => {
+                    // an escaped character is copied with its backslash
+                    let len = 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+                    filled.push_str(&rest[..len]);
+                    rest = &rest[len..];
+                    continue;
+                }
+                '<' if !tag => tag = true,
+                '>' if tag && quote.is_none() => tag = false,
+                '\'' | '"' if tag => {
+                    quote = match quote {
+                        None => Some(c),
+                        Some(q) if q == c => None,
+                        q => q,
+                    }
+                }
+                '{' | '}' if rest.starts_with("{{") || rest.starts_with("}}") => {
+                    filled.push(c);
+                    rest = &rest[2..];
+                    continue;
+                }
+                '{' => {
+                    let arg = rest.find('}').and_then(|end| {
+                        Some((end, args.get(rest[1..end].parse::<usize>().ok()?)?))
+                    });
+                    if let Some((end, arg)) = arg {
+                        let arg = arg.to_string();
+                        match (tag, quote) {
+                            (false, _) => escape(&mut filled, &arg),
+                            (true, Some(quote)) => {
+                                filled.extend(arg.chars().filter(|&c| c != quote))
+                            }
+                            (true, None) => {
+                                filled.push('"');
+                                filled.extend(arg.chars().filter(|&c| c != '"'));
+                                filled.push('"');
+                            }
+                        }
+                        rest = &rest[end + 1..];
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            filled.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        filled.push_str(rest);
+        filled
     }
+}
 
-    fn add_part(&mut self) {
-        let text = self.source[self.start..self.current].to_string();
-        self.parts.push(text);
+/// Writes `text` with the characters which would start markup escaped.
+fn escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(c, '<' | '>' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
     }
 }
@@ -3,12 +3,23 @@
 
 //! # Ziyy's core library
 
+use std::rc::Rc;
+
+pub use builder::Doc;
+pub use cache::Cache;
 pub use error::{Error, ErrorType, Result};
+pub use hyperlink::file_url;
 pub use indexer::Indexer;
-pub use parser::{Parser, WordParser, chunk::Chunk};
+pub use live::{Live, LiveRegion};
+pub use options::RenderOptions;
+pub use parser::{
+    Parser, WordParser,
+    chunk::Chunk,
+    tag_parer::tag::{Tag, TagType},
+};
 pub use resolver::{
     Resolver,
-    document::{Document, Node},
+    document::{Document, Edge, FrozenDocument, FrozenNode, Node, Selector},
 };
 pub use splitter::{
     Splitter,
@@ -17,16 +28,30 @@ pub use splitter::{
 
 pub use common::{Position, Span};
 pub use parser::color::Color;
+pub use stream::Streamer;
+pub use style_set::StyleSet;
+pub use term::{ColorLevel, Graphics, Icons, terminal_width};
+pub use width::{Graphemes, display_width, graphemes};
 
+mod builder;
 mod builtin;
+mod cache;
 mod error;
+mod hyperlink;
+mod image;
 #[macro_use]
 mod scanner;
 mod common;
 mod indexer;
+mod live;
+mod options;
 mod parser;
 mod resolver;
 mod splitter;
+mod stream;
+mod style_set;
+mod term;
+mod width;
 
 // mod ziyy;
 
@@ -53,19 +78,50 @@ pub fn style<T: AsRef<str>>(source: T) -> String {
 
 /// Styles the given text using ziyy.
 pub fn try_style<T: AsRef<str>>(source: T) -> Result<String> {
-    let mut indexer = Indexer::new();
-    let source = indexer.index(source.as_ref().to_string());
-    let mut splitter = Splitter::new();
-    #[allow(clippy::unnecessary_to_owned)]
-    let frags = splitter.split(source)?;
-
-    let parser = Parser::new(false);
-    let chunks = parser.parse(frags)?;
-
-    let mut resolver = Resolver::new(false);
-    let output = resolver.resolve(chunks)?;
+    try_style_for(source, ColorLevel::TrueColor)
+}
 
+/// Styles the given text using ziyy, rendered with `options` such as the
+/// colors of a [`ColorLevel`].
+///
+/// The text is parsed on every call. Templates styled again and again can be
+/// rendered through a [`Cache`] instead, which parses them only once.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, try_style_for};
+///
+/// let styled_text = try_style_for("<c rgb='255,0,0'>red</c>", ColorLevel::Ansi256).unwrap();
+/// assert_eq!(styled_text, "\x1b[38;5;196mred\x1b[39m");
+/// ```
+pub fn try_style_for<T: AsRef<str>>(
+    source: T,
+    options: impl Into<RenderOptions>,
+) -> Result<String> {
+    let document = resolve(source, options)?;
     let mut buf = String::new();
-    output.root().to_string(&mut buf);
+    document.root().to_string(&mut buf);
     Ok(buf)
 }
+
+/// Parses and resolves the given text into a document rendered with
+/// `options`, which can be queried and changed before it is rendered.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, resolve};
+///
+/// let doc = resolve("<b>a</b> <i>b</i>", ColorLevel::TrueColor).unwrap();
+/// doc.select("i").unwrap().unwrap().remove().unwrap();
+/// assert_eq!(doc.freeze().render(), "\x1b[1ma\x1b[22m ");
+/// ```
+pub fn resolve<T: AsRef<str>>(
+    source: T,
+    options: impl Into<RenderOptions>,
+) -> Result<Rc<Document>> {
+    options.into().parse(&Indexer::new().index(source.as_ref()))
+}
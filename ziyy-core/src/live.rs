@@ -0,0 +1,357 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::{
+    Document, Indexer, RenderOptions, Result, WordParser, display_width, parser::ansi::Ansi,
+    terminal_width,
+};
+
+/// Renders a template again and again into the same lines of a terminal.
+///
+/// The placeholders of the template, such as the `value` of a `<progress>`,
+/// are filled in before it is resolved, and every frame is drawn over the
+/// previous one by a [`LiveRegion`].
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, Live};
+///
+/// let mut live = Live::new("<spinner style='line' frame='{}'/> {}", ColorLevel::TrueColor);
+/// assert_eq!(live.render(&[&0, &"loading"]).unwrap(), "- loading");
+/// assert_eq!(live.render(&[&1, &"loading"]).unwrap(), "\r\x1b[2K\r\\ loading");
+/// ```
+pub struct Live {
+    template: String,
+    options: RenderOptions,
+    indexer: Indexer,
+    region: LiveRegion,
+}
+
+impl Live {
+    /// Creates a new Live that renders `template` with `options`, such as
+    /// the colors of a [`ColorLevel`](crate::ColorLevel).
+    pub fn new(template: impl Into<String>, options: impl Into<RenderOptions>) -> Self {
+        let options = options.into();
+        let mut region = LiveRegion::new();
+        region.set_width(options.width);
+        Self {
+            template: template.into(),
+            options,
+            indexer: Indexer::new(),
+            region,
+        }
+    }
+
+    /// Sets the width blocks are laid out in and lines wrap at, which
+    /// defaults to the width of the terminal.
+    pub fn set_width(&mut self, width: Option<usize>) {
+        self.options.width = width;
+        self.region.set_width(width);
+    }
+
+    /// Renders the template with its placeholders replaced by `args`,
+    /// preceded by the escape sequences which replace the previous frame.
+    pub fn render(&mut self, args: &[&dyn Display]) -> Result<String> {
+        let source = self.indexer.fill(&self.template, args);
+        let options = RenderOptions {
+            width: self.options.width.or_else(terminal_width),
+            ..self.options
+        };
+        // every frame is new, so it is resolved like `try_style_for` does
+        // without filling its cache
+        let output = options.parse(&source)?;
+
+        Ok(self.region.render(&output))
+    }
+
+    /// Forgets the previous frame, so that the next one is written after it
+    /// instead of replacing it.
+    pub fn reset(&mut self) {
+        self.region.reset();
+    }
+}
+
+/// Draws documents over each other in the same lines of a terminal.
+///
+/// The region remembers the lines of the previous frame and how many rows
+/// they wrapped to. A new frame moves the cursor back to the first line that
+/// differs and only redraws the lines that changed, with the styles that are
+/// in effect at their start. When the width changes, as when the terminal
+/// is resized, the previous frame is erased and the new one drawn in full.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::LiveRegion;
+///
+/// let mut region = LiveRegion::new();
+/// region.set_width(Some(80));
+/// assert_eq!(region.render_str("a\nb\nc"), "a\nb\nc");
+/// assert_eq!(region.render_str("a\nB\nc"), "\r\x1b[1A\x1b[2K\rB\r\x1b[1B\x1b[2G");
+/// ```
+#[derive(Default)]
+pub struct LiveRegion {
+    width: Option<usize>,
+    drawn: Option<Frame>,
+}
+
+/// The lines of a frame and the width they were drawn in.
+struct Frame {
+    width: Option<usize>,
+    /// Every line, preceded by the styles and the hyperlink in effect at its
+    /// start.
+    lines: Vec<String>,
+    /// The length of the escape sequences every line is preceded by.
+    states: Vec<usize>,
+    /// The number of rows every line wraps to.
+    rows: Vec<usize>,
+}
+
+impl Frame {
+    fn new(text: &str, width: Option<usize>) -> Self {
+        let mut lines = Vec::new();
+        let mut states = Vec::new();
+        // escapes are folded as they come, so that every line is preceded by
+        // one style and one hyperlink however many came before it
+        let mut style = Ansi::new();
+        let mut link = "";
+        for line in text.split('\n') {
+            let state = format!("{style}{link}");
+            states.push(state.len());
+            lines.push(state + line);
+            for escape in escapes(line).1 {
+                match escape.strip_prefix("\x1b[") {
+                    Some(params) => fold(&mut style, &params[..params.len() - 1]),
+                    None if is_link_end(escape) => link = "",
+                    None => link = escape,
+                }
+            }
+        }
+        let rows = lines.iter().map(|line| rows(line, width)).collect();
+        Self {
+            width,
+            lines,
+            states,
+            rows,
+        }
+    }
+
+    /// Returns the row every line starts at and the number of rows.
+    fn starts(&self) -> (Vec<usize>, usize) {
+        let mut starts = Vec::with_capacity(self.rows.len());
+        let mut total = 0;
+        for rows in &self.rows {
+            starts.push(total);
+            total += rows;
+        }
+        (starts, total)
+    }
+}
+
+impl LiveRegion {
+    /// Creates a new LiveRegion that has not drawn anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width lines wrap at. Without one, the width of the terminal
+    /// is queried for every frame.
+    pub fn set_width(&mut self, width: Option<usize>) {
+        self.width = width;
+    }
+
+    /// Returns the output which draws `document` over the previous frame.
+    pub fn render(&mut self, document: &Document) -> String {
+        let mut buf = String::new();
+        document.root().to_string(&mut buf);
+        self.render_str(&buf)
+    }
+
+    /// Returns the output which draws the rendered text `text` over the
+    /// previous frame.
+    pub fn render_str(&mut self, text: &str) -> String {
+        let width = self.width.or_else(terminal_width);
+        let frame = Frame::new(text, width);
+        let Some(old) = self.drawn.replace(frame) else {
+            return text.to_string();
+        };
+        let frame = self.drawn.as_ref().unwrap();
+
+        let mut out = String::new();
+        if old.width != width {
+            // the terminal has wrapped the previous frame again at the new
+            // width, if it reflows text at all
+            let rows: usize = old.lines.iter().map(|line| rows(line, width)).sum();
+            moved(&mut out, rows - 1, 0);
+            out.push_str("\x1b[J");
+            out.push_str(text);
+            return out;
+        }
+        if old.lines == frame.lines {
+            return out;
+        }
+
+        let (old_starts, old_total) = old.starts();
+        let (starts, total) = frame.starts();
+        let mut row = old_total - 1;
+        let mut drawn = None;
+        for (i, line) in frame.lines.iter().enumerate() {
+            let old_line = old.lines.get(i).filter(|_| old_starts[i] == starts[i]);
+            if old_line == Some(line) {
+                // keep the styles of the terminal in step with the frame
+                if drawn.is_some() {
+                    out.extend(escapes(&line[frame.states[i]..]).1);
+                }
+                continue;
+            }
+            if old_line.is_none() || old.rows[i] != frame.rows[i] {
+                // the line moved or changed its height, so the rest of the
+                // frame is drawn anew
+                if starts[i] < old_total {
+                    moved(&mut out, row, starts[i]);
+                    out.push_str("\x1b[J");
+                } else {
+                    moved(&mut out, row, old_total - 1);
+                    out.push('\n');
+                }
+                out.push_str(&frame.lines[i]);
+                for (line, state) in frame.lines.iter().zip(&frame.states).skip(i + 1) {
+                    out.push('\n');
+                    out.push_str(&line[*state..]);
+                }
+                return out;
+            }
+
+            // erase every row of the line, so that nothing of it is left
+            for r in starts[i]..starts[i] + frame.rows[i] {
+                moved(&mut out, row, r);
+                row = r;
+                out.push_str("\x1b[2K");
+            }
+            moved(&mut out, row, starts[i]);
+            out.push_str(line);
+            row = starts[i] + frame.rows[i] - 1;
+            drawn = Some(i);
+        }
+
+        if total < old_total {
+            moved(&mut out, row, total);
+            out.push_str("\x1b[J");
+            row = total;
+            drawn = None;
+        }
+        if drawn != Some(frame.lines.len() - 1) {
+            // move to the end of the last line, which was not drawn again
+            moved(&mut out, row, total - 1);
+            let line = frame.lines.last().unwrap();
+            let used = display_width(&escapes(line).0);
+            let column = match width {
+                Some(width) if width > 0 => {
+                    (used - (rows(line, Some(width)) - 1) * width).min(width - 1)
+                }
+                _ => used,
+            };
+            if column > 0 {
+                out.push_str(&format!("\x1b[{}G", column + 1));
+            }
+        }
+        out
+    }
+
+    /// Returns the output which erases the previous frame, and forgets it.
+    pub fn clear(&mut self) -> String {
+        let Some(old) = self.drawn.take() else {
+            return String::new();
+        };
+        let width = self.width.or_else(terminal_width);
+        let rows: usize = old.lines.iter().map(|line| rows(line, width)).sum();
+        let mut out = String::new();
+        moved(&mut out, rows - 1, 0);
+        out.push_str("\x1b[J");
+        out
+    }
+
+    /// Forgets the previous frame, so that the next one is written after it
+    /// instead of replacing it.
+    pub fn reset(&mut self) {
+        self.drawn = None;
+    }
+}
+
+/// Moves the cursor from row `from` to the start of row `to`.
+fn moved(out: &mut String, from: usize, to: usize) {
+    out.push('\r');
+    match to.cmp(&from) {
+        Ordering::Less => out.push_str(&format!("\x1b[{}A", from - to)),
+        Ordering::Greater => out.push_str(&format!("\x1b[{}B", to - from)),
+        Ordering::Equal => {}
+    }
+}
+
+/// Returns the number of rows `line` takes up when wrapped at `width`.
+fn rows(line: &str, width: Option<usize>) -> usize {
+    match width {
+        Some(width) if width > 0 => display_width(&escapes(line).0).div_ceil(width).max(1),
+        _ => 1,
+    }
+}
+
+/// Folds the parameters of an SGR escape sequence into `style`.
+fn fold(style: &mut Ansi, params: &str) {
+    let (first, rest) = params.split_once(';').unwrap_or((params, ""));
+    let params = match first {
+        "" | "0" => {
+            *style = Ansi::new();
+            rest
+        }
+        _ => params,
+    };
+    if params.is_empty() {
+        return;
+    }
+    if let Ok(tag) = WordParser::ansi_to_tag(params) {
+        *style += Ansi::clone(&tag);
+    }
+}
+
+/// Returns true if the OSC 8 escape sequence `escape` ends a hyperlink.
+fn is_link_end(escape: &str) -> bool {
+    escape
+        .strip_prefix("\x1b]8;")
+        .and_then(|rest| rest.split_once(';'))
+        .is_some_and(|(_, uri)| matches!(uri, "\x07" | "\x1b\\"))
+}
+
+/// Splits `line` into its visible text and the styles and hyperlinks among
+/// it. Other escape sequences are dropped.
+fn escapes(line: &str) -> (String, Vec<&str>) {
+    let mut text = String::with_capacity(line.len());
+    let mut escapes = Vec::new();
+    let mut rest = line;
+    while let Some(i) = rest.find('\x1b') {
+        text.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let end = match rest.as_bytes().get(1) {
+            Some(b'[') => rest[2..]
+                .find(|c: char| ('@'..='~').contains(&c))
+                .map_or(rest.len(), |n| n + 3),
+            Some(b']') => match (rest.find('\x07'), rest.find("\x1b\\")) {
+                (Some(bel), Some(st)) if st < bel => st + 2,
+                (Some(bel), _) => bel + 1,
+                (None, Some(st)) => st + 2,
+                (None, None) => rest.len(),
+            },
+            Some(_) => 2,
+            None => 1,
+        };
+        let escape = &rest[..end];
+        if (escape.starts_with("\x1b[") && escape.ends_with('m')) || escape.starts_with("\x1b]8;") {
+            escapes.push(escape);
+        }
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+    (text, escapes)
+}
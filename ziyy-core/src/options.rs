@@ -0,0 +1,108 @@
+use std::{
+    io::{IsTerminal, stdout},
+    rc::Rc,
+};
+
+use crate::{
+    Chunk, ColorLevel, Document, Graphics, Icons, Parser, Resolver, Result, Splitter,
+    terminal_width,
+};
+
+/// What the output of ziyy is rendered for: the colors, width, links, images
+/// and icons the place it is written to is able to show.
+///
+/// The default renders like [`try_style`](crate::try_style), with 24-bit
+/// colors, hyperlinks, images drawn with half blocks and no width.
+/// [`detect`](RenderOptions::detect) fits the output to the terminal attached
+/// to stdout instead. Shortcodes such as `:check:` are only expanded when
+/// `icons` is set, usually to [`Icons::detect`]. A [`ColorLevel`] converts
+/// into the default options with its colors.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, Icons, RenderOptions, try_style_for};
+///
+/// let options = RenderOptions {
+///     level: ColorLevel::None,
+///     icons: Icons::Ascii,
+///     ..RenderOptions::default()
+/// };
+/// assert_eq!(try_style_for("<b>:check:</b> done", options).unwrap(), "[ok] done");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderOptions {
+    /// Colors the output may use.
+    pub level: ColorLevel,
+    /// Width blocks are laid out in and lines wrap at.
+    pub width: Option<usize>,
+    /// Whether links are written as OSC 8 hyperlinks, or as their text
+    /// followed by their target.
+    pub hyperlinks: bool,
+    /// Whether `cursor`, `erase`, `title`, `bell` and `screen` elements write
+    /// their control sequences, or nothing.
+    pub controls: bool,
+    /// How `img` elements are shown.
+    pub graphics: Graphics,
+    /// How `:name:` shortcodes are expanded, if at all.
+    pub icons: Icons,
+}
+
+impl RenderOptions {
+    /// Detects the options of the terminal attached to stdout. Output which
+    /// does not go to a terminal gets links as text and no control
+    /// sequences. Shortcodes are left as they are.
+    pub fn detect() -> Self {
+        let terminal = stdout().is_terminal();
+        Self {
+            level: ColorLevel::current(),
+            width: terminal_width(),
+            hyperlinks: terminal,
+            controls: terminal,
+            graphics: Graphics::detect(),
+            icons: Icons::None,
+        }
+    }
+
+    /// Parses and resolves `source`, whose placeholders are already numbered
+    /// or filled in, into a document rendered with these options.
+    pub(crate) fn parse(&self, source: &str) -> Result<Rc<Document>> {
+        let frags = Splitter::new().split(source)?;
+        let chunks = Parser::new(false).parse(frags)?;
+        self.resolve(chunks)
+    }
+
+    /// Resolves `chunks` into a document rendered with these options.
+    pub(crate) fn resolve(&self, chunks: Vec<Chunk>) -> Result<Rc<Document>> {
+        let mut resolver = Resolver::new(false);
+        resolver.set_options(self);
+        let output = resolver.resolve(chunks)?;
+        if self.level != ColorLevel::TrueColor {
+            output.root().downgrade_colors(self.level);
+        }
+        Ok(output)
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            level: ColorLevel::TrueColor,
+            width: None,
+            hyperlinks: true,
+            controls: true,
+            graphics: Graphics::Blocks,
+            icons: Icons::None,
+        }
+    }
+}
+
+impl From<ColorLevel> for RenderOptions {
+    fn from(level: ColorLevel) -> Self {
+        Self {
+            level,
+            ..Self::default()
+        }
+    }
+}
@@ -97,7 +97,6 @@ pub enum DuoEffect {
 
 impl DuoEffect {
     /// If any effect is set
-
     pub fn is_set(&self) -> bool {
         !matches!(self, DuoEffect::None)
     }
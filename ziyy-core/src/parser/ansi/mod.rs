@@ -1,4 +1,5 @@
 use crate::parser::color::Color;
+use crate::term::ColorLevel;
 // pub use effect::{DuoEffect, Effect};
 pub use options::AnsiOptions;
 use std::fmt::{Debug, Display, Write};
@@ -56,6 +57,12 @@ impl Ansi {
         self.set_fg_color(Color::four_bit(39));
         self.set_bg_color(Color::four_bit(49));
     }
+
+    /// Converts the colors of this style to the closest colors available at `level`.
+    pub fn downgrade(&mut self, level: ColorLevel) {
+        self.set_fg_color(self.fg_color().downgrade(level));
+        self.set_bg_color(self.bg_color().downgrade(level));
+    }
 }
 
 fn get_style(style: &u32, offset: u32) -> bool {
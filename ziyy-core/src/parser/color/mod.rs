@@ -1,6 +1,7 @@
 use crate::common::Span;
 use crate::error::{Error, ErrorType};
 use crate::scanner::GenericScanner;
+use crate::term::ColorLevel;
 pub use number::Number;
 use scanner::Scanner;
 use std::collections::VecDeque;
@@ -17,7 +18,7 @@ macro_rules! number {
     ( $token:expr ) => {
         match $token.r#type {
             NUMBER => $token.literal.unwrap().into(),
-            PLACE_HOLDER => $token.lexeme.clone().into(),
+            PLACE_HOLDER => $token.lexeme.to_string().into(),
             _ => {
                 return Err(Error::new(
                     ErrorType::InvalidNumber,
@@ -88,7 +89,10 @@ impl Color {
         Color::String(s)
     }
 
-    fn parse_rgb(mut next: impl FnMut() -> Result<Token, Error>, n: u8) -> Result<Color, Error> {
+    fn parse_rgb<'a>(
+        mut next: impl FnMut() -> Result<Token<'a>, Error>,
+        n: u8,
+    ) -> Result<Color, Error> {
         let token = next()?;
 
         expect(&token, LEFT_PAREN, ErrorType::UnexpectedToken)?;
@@ -155,7 +159,10 @@ impl Color {
         Color::Rgb(Rgb(r, g, b, n))
     }
 
-    fn parsed_fixed(mut next: impl FnMut() -> Result<Token, Error>, n: u8) -> Result<Color, Error> {
+    fn parsed_fixed<'a>(
+        mut next: impl FnMut() -> Result<Token<'a>, Error>,
+        n: u8,
+    ) -> Result<Color, Error> {
         let token = next()?;
         expect(&token, LEFT_PAREN, ErrorType::UnexpectedToken)?;
 
@@ -192,10 +199,11 @@ impl Color {
     }
 
     pub fn fg_hex(h: &str) -> Color {
+        let lexeme = format!("f{h}");
         Color::parse_hex(
             &Token {
                 r#type: TokenType::NUMBER,
-                lexeme: format!("f{h}"),
+                lexeme: &lexeme,
                 literal: None,
                 span: Span::inserted(),
             },
@@ -204,10 +212,11 @@ impl Color {
     }
 
     pub fn bg_hex(h: &str) -> Color {
+        let lexeme = format!("b{h}");
         Color::parse_hex(
             &Token {
                 r#type: TokenType::NUMBER,
-                lexeme: format!("b{h}"),
+                lexeme: &lexeme,
                 literal: None,
                 span: Span::inserted(),
             },
@@ -218,6 +227,100 @@ impl Color {
     pub fn is_empty(&self) -> bool {
         self.to_string().is_empty()
     }
+
+    /// Converts this color to the closest color available at `level`.
+    pub fn downgrade(&self, level: ColorLevel) -> Color {
+        match (self, level) {
+            (_, ColorLevel::None) => Color::new(),
+            (Color::Rgb(Rgb(r, g, b, n)), ColorLevel::Ansi256) => {
+                Color::Ansi256(Ansi256(rgb_to_ansi256(*r, *g, *b), *n))
+            }
+            (Color::Rgb(Rgb(r, g, b, n)), ColorLevel::Ansi16) => {
+                Color::four_bit(rgb_to_ansi16(*r, *g, *b, *n))
+            }
+            (Color::Ansi256(Ansi256(i, n)), ColorLevel::Ansi16) => {
+                let (r, g, b) = ansi256_to_rgb(*i);
+                Color::four_bit(rgb_to_ansi16(r, g, b, *n))
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+/// The xterm defaults for the 16 standard colors.
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..8 => 16,
+            249.. => 231,
+            _ => 232 + ((r as u16 - 8) * 24 / 247) as u8,
+        };
+    }
+
+    let cube = |v: u8| match v {
+        0..48 => 0,
+        48..115 => 1,
+        _ => (v - 35) / 40,
+    };
+
+    16 + 36 * cube(r) + 6 * cube(g) + cube(b)
+}
+
+fn ansi256_to_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..16 => ANSI16_PALETTE[i as usize],
+        16..232 => {
+            let i = i - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+/// Returns the 4-bit code of the palette color closest to the given color.
+/// `n` is 38 for foreground colors and 48 for background colors.
+fn rgb_to_ansi16(r: u8, g: u8, b: u8, n: u8) -> u8 {
+    let distance = |(pr, pg, pb): (u8, u8, u8)| {
+        let dr = r as i32 - pr as i32;
+        let dg = g as i32 - pg as i32;
+        let db = b as i32 - pb as i32;
+        dr * dr + dg * dg + db * db
+    };
+
+    let i = (0..16u8)
+        .min_by_key(|i| distance(ANSI16_PALETTE[*i as usize]))
+        .unwrap_or(0);
+
+    let base = if n == 48 { 40 } else { 30 };
+    if i < 8 { base + i } else { base + 60 + i - 8 }
 }
 
 impl TryFrom<(String, Span)> for Color {
@@ -227,7 +330,7 @@ impl TryFrom<(String, Span)> for Color {
         if source.0.is_empty() {
             return Ok(Color::String(source.0));
         }
-        let mut scanner = Scanner::new(source.0, source.1);
+        let mut scanner = Scanner::new(&source.0, source.1);
         let mut tokens: VecDeque<_> = scanner.scan_tokens().into();
         //println!("{:?}", tokens);
         //let line = tokens[0].line;
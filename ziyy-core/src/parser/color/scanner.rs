@@ -1,5 +1,5 @@
 use crate::common::Span;
-use crate::scanner::{GenericScanner, Source, is_alpha, is_alpha_numeric, is_digit, is_hexdigit};
+use crate::scanner::{GenericScanner, is_alpha, is_alpha_numeric, is_digit, is_hexdigit};
 
 use super::token::Token;
 use super::token::TokenType::{self, *};
@@ -51,9 +51,9 @@ macro_rules! shrink {
     }};
 }
 
-pub struct Scanner {
-    source: Vec<char>,
-    tokens: Vec<Token>,
+pub struct Scanner<'a> {
+    source: &'a str,
+    tokens: Vec<Token<'a>>,
     start: usize,
     current: usize,
     span: Span,
@@ -84,13 +84,13 @@ impl_generic_scanner!(|s: &mut Scanner| {
     }
 });
 
-impl Scanner {
-    pub fn new(source: String, mut span: Span) -> Self {
+impl<'a> Scanner<'a> {
+    pub fn new(source: &'a str, mut span: Span) -> Self {
         span.tie_start();
 
         Self {
-            source: source.chars().collect(),
-            tokens: vec![],
+            source,
+            tokens: Vec::with_capacity(8),
             start: 0,
             current: 0,
             span,
@@ -102,9 +102,9 @@ impl Scanner {
             self.advance();
         }
 
-        let k = self.source[self.start..self.current].to_string();
+        let k = &self.source[self.start..self.current];
 
-        if let Some(r#type) = COLORS.get(k.as_str()) {
+        if let Some(r#type) = COLORS.get(k) {
             self.add_token(*r#type);
         } else {
             self.add_token(IDENTIFIER);
@@ -123,10 +123,10 @@ impl Scanner {
             }
         }
 
-        let value = self.source[self.start..self.current].to_string();
+        let value = &self.source[self.start..self.current];
         self.add_token2(
             NUMBER,
-            Some(f64::from_str(value.as_str()).unwrap().round()).map(|x| shrink!(x)),
+            Some(f64::from_str(value).unwrap().round()).map(|x| shrink!(x)),
         );
     }
 
@@ -162,24 +162,8 @@ impl Scanner {
     }
 
     fn add_token2(&mut self, r#type: TokenType, literal: Option<u8>) {
-        let text = self.source[self.start..self.current].to_string();
+        let text = &self.source[self.start..self.current];
         self.tokens
             .push(Token::new(r#type, text, literal, self.span));
     }
 }
-
-trait ToString {
-    fn to_string(&self) -> String;
-}
-
-impl ToString for [char] {
-    fn to_string(&self) -> String {
-        let mut text = String::with_capacity(self.len());
-
-        for ch in self {
-            text.push(*ch)
-        }
-
-        text
-    }
-}
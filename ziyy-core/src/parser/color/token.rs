@@ -39,15 +39,15 @@ pub enum TokenType {
 }
 
 #[derive(Debug)]
-pub struct Token {
+pub struct Token<'a> {
     pub r#type: TokenType,
-    pub lexeme: String,
+    pub lexeme: &'a str,
     pub literal: Option<u8>,
     pub span: Span,
 }
 
-impl Token {
-    pub fn new(r#type: TokenType, lexeme: String, literal: Option<u8>, span: Span) -> Self {
+impl<'a> Token<'a> {
+    pub fn new(r#type: TokenType, lexeme: &'a str, literal: Option<u8>, span: Span) -> Self {
         Token {
             r#type,
             literal,
@@ -25,7 +25,7 @@ impl Parser {
         Self { parse_placeholders }
     }
 
-    pub fn parse(&self, frags: Vec<Fragment>) -> Result<Vec<Chunk>> {
+    pub fn parse(&self, frags: Vec<Fragment<'_>>) -> Result<Vec<Chunk>> {
         let mut tag_parser = tag_parer::TagParser::new(self.parse_placeholders);
         // let word_parer = WordParser::new();
         let mut chunks = vec![];
@@ -42,7 +42,7 @@ impl Parser {
                 FragmentType::Whitespace => {
                     // Handle whitespace fragments
                     chunks.push(Chunk {
-                        data: ChunkData::WhiteSpace(frag.lexeme),
+                        data: ChunkData::WhiteSpace(frag.lexeme.to_string()),
                         span,
                     });
                 }
@@ -52,7 +52,7 @@ impl Parser {
                     // let chs = word_parer.parse(frag)?;
                     // chunks.extend_from_slice(&chs);
                     chunks.push(Chunk {
-                        data: ChunkData::Word(frag.lexeme),
+                        data: ChunkData::Word(frag.lexeme.to_string()),
                         span,
                     });
                 }
@@ -1,3 +1,4 @@
+use crate::common::Span;
 use crate::error::{Error, ErrorType};
 use crate::{scanner::GenericScanner, splitter::fragment::Fragment};
 use scanner::Scanner;
@@ -29,12 +30,26 @@ macro_rules! assign_prop {
         if $token.r#type == EQUAL {
             $token = $next()?;
             expect(&$token, STRING, ErrorType::InvalidTagAttributeValue)?;
-            $tag.$set_prop($token.literal.unwrap());
+            $tag.$set_prop($token.literal.unwrap().to_string());
             $token = $next()?;
         }
     }};
 }
 
+macro_rules! assign_attribute {
+    ( $tag:expr, $name:expr, $next:expr, $token:expr ) => {{
+        $token = $next()?;
+        if $token.r#type == EQUAL {
+            $token = $next()?;
+            expect(&$token, STRING, ErrorType::InvalidTagAttributeValue)?;
+            $tag.set_attribute($name, $token.literal.unwrap().to_string());
+            $token = $next()?;
+        } else {
+            $tag.set_attribute($name, String::new());
+        }
+    }};
+}
+
 macro_rules! assign_prop_color {
     ( $tag:expr, $set_prop:tt, $next:expr, $token:expr, $pre:expr ) => {{
         $token = $next()?;
@@ -137,7 +152,7 @@ impl TagParser {
         }
     }
 
-    pub fn parse(&mut self, source: Fragment) -> Result<Tag, Error> {
+    pub fn parse(&mut self, source: Fragment<'_>) -> Result<Tag, Error> {
         let mut scanner = Scanner::new(source.clone());
         let tokens = scanner.scan_tokens();
         let open = &tokens[0].r#type;
@@ -175,42 +190,13 @@ impl TagParser {
 
         let mut tag = Tag::default();
         tag.r#type = tag_type;
-        tag.set_name(token.lexeme.clone());
+        tag.set_name(token.lexeme.to_string());
 
-        match tag.name().to_lowercase().as_str() {
-            "b" | "strong" => {
-                tag.set_brightness(DuoEffect::A);
-            }
-            "d" | "dim" => {
-                tag.set_brightness(DuoEffect::B);
-            }
-            "i" | "em" => {
-                tag.set_italics(Effect::Apply);
-            }
-            "u" | "ins" => {
-                tag.set_under(DuoEffect::A);
-            }
-            "uu" => {
-                tag.set_under(DuoEffect::B);
-            }
-            "k" | "blink" => {
-                tag.set_blink(Effect::Apply);
-            }
-            "r" => {
-                tag.set_negative(Effect::Apply);
-            }
-            "h" => {
-                tag.set_hidden(Effect::Apply);
-            }
-            "s" | "del" => {
-                tag.set_strike(Effect::Apply);
-            }
-            _ => {}
-        }
+        tag.apply_name_styles();
 
         let mut token = next()?;
         while token.r#type == IDENTIFIER {
-            match token.lexeme.as_str() {
+            match token.lexeme {
                 "b" | "bold" => {
                     assign_prop_duoeffect!(
                         tag,
@@ -276,14 +262,11 @@ impl TagParser {
                     }
 
                     let color = |pre: &str| -> Result<_, _> {
+                        let prefix = format!("{pre}fixed(");
                         let c: Color = (
-                            format!("{pre}fixed({})", token2.literal.unwrap()),
-                            // move start of span back by 7 columns due to inserted text ffixed( or bfixed( to preserve span of color in string
-                            // fixed = "..."
-                            // ^^^^^ ^ ^
-                            // ||||| | |
-                            // 12345 6 7
-                            token2.span.unquote() - (0, 7),
+                            format!("{prefix}{})", token2.literal.unwrap()),
+                            // move start of span back over the inserted fixed( to preserve span of color in string
+                            token2.span.unquote().before(&prefix),
                         )
                             .try_into()?;
                         Ok(c)
@@ -308,14 +291,11 @@ impl TagParser {
                     }
 
                     let color = |pre: &str| -> Result<_, _> {
+                        let prefix = format!("{pre}rgb(");
                         let c: Color = (
-                            format!("{pre}rgb({})", token2.literal.unwrap()),
-                            // move start of span back by 5 columns due to inserted text frgb( or brgb( to preserve span of color in string
-                            // rgb = "..."
-                            // ^^^ ^ ^
-                            // ||| | |
-                            // 123 4 5
-                            token2.span.unquote() - (0, 5),
+                            format!("{prefix}{})", token2.literal.unwrap()),
+                            // move start of span back over the inserted rgb( to preserve span of color in string
+                            token2.span.unquote().before(&prefix),
                         )
                             .try_into()?;
                         Ok(c)
@@ -351,29 +331,19 @@ impl TagParser {
                     }
                 }
 
-                "n" => {
-                    if tag.name() == "br" {
-                        assign_prop!(tag, set_custom, next, token);
-                    } else {
-                        consume_declaration!(tag, next, token);
-                    }
+                "n" if matches!(tag.name().as_str(), "br" | "space") => {
+                    assign_prop!(tag, set_custom, next, token)
                 }
-                "href" => {
-                    if tag.name() == "a" {
-                        assign_prop!(tag, set_custom, next, token);
-                    } else {
-                        consume_declaration!(tag, next, token);
-                    }
-                }
-                "id" => {
-                    if tag.name() == "let" {
-                        assign_prop!(tag, set_custom, next, token);
-                    } else {
-                        consume_declaration!(tag, next, token);
-                    }
+                "href" if tag.name() == "a" => assign_prop!(tag, set_custom, next, token),
+                name @ ("id" | "file") if tag.name() == "a" => {
+                    assign_attribute!(tag, name, next, token)
                 }
+                "id" if tag.name() == "let" => assign_prop!(tag, set_custom, next, token),
                 "indent" => {
-                    if matches!(tag.name().as_str(), "p" | "table") {
+                    if matches!(
+                        tag.name().as_str(),
+                        "p" | "div" | "table" | "ul" | "ol" | "box"
+                    ) {
                         assign_prop!(tag, set_custom, next, token);
                     } else {
                         consume_declaration!(tag, next, token);
@@ -382,6 +352,84 @@ impl TagParser {
 
                 "class" => assign_prop!(tag, set_class, next, token),
 
+                name @ ("width" | "hang" | "align" | "min-width" | "max-width" | "truncate")
+                    if matches!(tag.name().as_str(), "p" | "div") =>
+                {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("width" | "align" | "min-width" | "max-width" | "truncate")
+                    if tag.name() == "span" =>
+                {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("marker" | "start") if matches!(tag.name().as_str(), "ul" | "ol") => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("border" | "padding" | "align") if tag.name() == "table" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("border" | "padding" | "title" | "width") if tag.name() == "box" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("tabsize" | "numbers")
+                    if matches!(tag.name().as_str(), "pre" | "code" | "diff") =>
+                {
+                    assign_attribute!(tag, name, next, token)
+                }
+                "lang" if tag.name() == "code" => assign_attribute!(tag, "lang", next, token),
+                "words" if tag.name() == "diff" => assign_attribute!(tag, "words", next, token),
+                name @ ("value" | "max" | "width" | "from" | "to" | "percent")
+                    if tag.name() == "progress" =>
+                {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("data" | "min" | "max" | "width") if tag.name() == "sparkline" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("value" | "max" | "width") if tag.name() == "bar" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                "thresholds" if matches!(tag.name().as_str(), "sparkline" | "bar") => {
+                    token = next()?;
+                    if token.r#type == EQUAL {
+                        token = next()?;
+                        expect(&token, STRING, ErrorType::InvalidTagAttributeValue)?;
+                        let thresholds = token.literal.unwrap();
+                        check_thresholds(thresholds, token.span.unquote())?;
+                        tag.set_attribute("thresholds", thresholds.to_string());
+                        token = next()?;
+                    } else {
+                        tag.set_attribute("thresholds", String::new());
+                    }
+                }
+                name @ ("frame" | "style") if tag.name() == "spinner" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("up" | "down" | "left" | "right" | "col" | "to" | "save" | "restore"
+                | "visible")
+                    if tag.name() == "cursor" =>
+                {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("line" | "screen") if tag.name() == "erase" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                "icon" if tag.name() == "title" => assign_attribute!(tag, "icon", next, token),
+                name @ ("alt" | "main") if tag.name() == "screen" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("src" | "width" | "height" | "alt") if tag.name() == "img" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("char" | "style" | "label" | "width") if tag.name() == "hr" => {
+                    assign_attribute!(tag, name, next, token)
+                }
+                name @ ("colspan" | "align" | "width" | "hang")
+                    if matches!(tag.name().as_str(), "td" | "th") =>
+                {
+                    assign_attribute!(tag, name, next, token)
+                }
+
                 _ => {
                     consume_declaration!(tag, next, token);
                 }
@@ -394,14 +442,14 @@ impl TagParser {
             }
             TagType::SelfClose => {}
             TagType::Close => {
-                if let Some(last) = self.stack.pop() {
-                    if last.name() != tag.name() {
-                        return Err(Error::new(
-                            ErrorType::InvalidTag,
-                            format!("Mismatched tag: {:?} {:?}", tag.name(), last.name()),
-                            token.span,
-                        ));
-                    }
+                if let Some(last) = self.stack.pop()
+                    && last.name() != tag.name()
+                {
+                    return Err(Error::new(
+                        ErrorType::InvalidTag,
+                        format!("Mismatched tag: {:?} {:?}", tag.name(), last.name()),
+                        token.span,
+                    ));
                 }
             }
         }
@@ -410,6 +458,37 @@ impl TagParser {
     }
 }
 
+/// Checks that `thresholds` is a list of `value:color` pairs separated by `;`,
+/// such as `60:yellow; 90:red`.
+fn check_thresholds(thresholds: &str, span: Span) -> Result<(), Error> {
+    for entry in thresholds.split(';').filter(|s| !s.trim().is_empty()) {
+        // colors hold no `:`, so a second one means a missing `;`
+        let pair = entry
+            .split_once(':')
+            .filter(|(_, color)| !color.contains(':'));
+        let Some((value, color)) = pair else {
+            return Err(Error::new(
+                ErrorType::InvalidTagAttributeValue,
+                format!(
+                    "{:?} is not a threshold, expected value:color separated by ';'",
+                    entry.trim()
+                ),
+                span,
+            ));
+        };
+        let value = value.trim();
+        if !value.parse::<f64>().is_ok_and(f64::is_finite) {
+            return Err(Error::new(
+                ErrorType::InvalidNumber,
+                format!("{value:?} is not a valid number"),
+                span,
+            ));
+        }
+        Color::try_from((format!("f{}", color.trim()), span))?;
+    }
+    Ok(())
+}
+
 fn expect(token: &Token, expected: token::TokenType, error: ErrorType) -> Result<(), Error> {
     if token.r#type == expected {
         Ok(())
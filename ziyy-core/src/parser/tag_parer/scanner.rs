@@ -4,25 +4,25 @@ use super::token::{
 };
 use crate::{
     common::Span,
-    scanner::{GenericScanner, Source, is_alpha, is_alpha_numeric},
+    scanner::{GenericScanner, is_alpha, is_alpha_numeric},
     splitter::fragment::Fragment,
 };
 
-pub struct Scanner {
-    source: Vec<char>,
-    tokens: Vec<Token>,
+pub struct Scanner<'a> {
+    source: &'a str,
+    tokens: Vec<Token<'a>>,
     start: usize,
     current: usize,
     span: Span,
 }
 
-impl Scanner {
-    pub fn new(mut source: Fragment) -> Self {
+impl<'a> Scanner<'a> {
+    pub fn new(mut source: Fragment<'a>) -> Self {
         source.span.tie_start();
 
         Self {
-            source: source.lexeme.chars().collect(),
-            tokens: vec![],
+            source: source.lexeme,
+            tokens: Vec::with_capacity(16),
             start: 0,
             current: 0,
             span: source.span,
@@ -30,7 +30,8 @@ impl Scanner {
     }
 
     fn identifier(&mut self) {
-        while is_alpha_numeric(self.peek()) {
+        // names such as `min-width` may contain dashes after the first letter
+        while is_alpha_numeric(self.peek()) || self.peek() == '-' {
             self.advance();
         }
 
@@ -39,21 +40,18 @@ impl Scanner {
 
     fn string(&mut self, c: char) {
         while self.peek() != c && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.span += (1, 0);
-            }
             self.advance();
         }
 
         if self.is_at_end() {
             // TODO: error
-            self.add_token2(ERROR, Some("Unterminated String.".to_owned()));
+            self.add_token2(ERROR, Some("Unterminated String."));
             return;
         }
 
         self.advance();
 
-        let value = self.source[self.start + 1..self.current - 1].to_string();
+        let value = &self.source[self.start + 1..self.current - 1];
         self.add_token2(STRING, Some(value));
     }
 
@@ -61,11 +59,11 @@ impl Scanner {
         if self.is_at_end() {
             return false;
         }
-        if self.source[self.current] != expected {
+        if self.peek() != expected {
             return false;
         }
 
-        self.current += 1;
+        self.advance();
         true
     }
 
@@ -73,8 +71,8 @@ impl Scanner {
         self.add_token2(r#type, None);
     }
 
-    fn add_token2(&mut self, r#type: TokenType, literal: Option<String>) {
-        let text = self.source[self.start..self.current].to_string();
+    fn add_token2(&mut self, r#type: TokenType, literal: Option<&'a str>) {
+        let text = &self.source[self.start..self.current];
         self.tokens
             .push(Token::new(r#type, text, literal, self.span));
     }
@@ -101,10 +99,7 @@ impl_generic_scanner!(|s: &mut Scanner| {
         '>' => s.add_token(GREATER),
         '/' => match_add!('>', SLASH_GREATER, SLASH),
         '<' => match_add!('/', LESS_SLASH, LESS),
-        ' ' | '\r' | '\t' => {}
-        '\n' => {
-            s.span += (1, 0);
-        }
+        ' ' | '\r' | '\t' | '\n' => {}
         '"' => s.string('"'),
         '\'' => s.string('\''),
 
@@ -113,24 +108,8 @@ impl_generic_scanner!(|s: &mut Scanner| {
                 s.identifier();
             } else {
                 // TODO: error
-                s.add_token2(ERROR, Some("Unexpected character.".to_owned()));
+                s.add_token2(ERROR, Some("Unexpected character."));
             }
         }
     }
 });
-
-trait ToString {
-    fn to_string(&self) -> String;
-}
-
-impl ToString for [char] {
-    fn to_string(&self) -> String {
-        let mut text = String::with_capacity(self.len());
-
-        for ch in self {
-            text.push(*ch)
-        }
-
-        text
-    }
-}
@@ -3,9 +3,16 @@ use std::{
     ops::{Add, Deref, DerefMut, Not, Sub},
 };
 
-use crate::parser::ansi::Ansi;
+use super::TagParser;
+use crate::{
+    common::Span,
+    error::Result,
+    parser::ansi::{Ansi, DuoEffect, Effect},
+    splitter::fragment::{Fragment, FragmentType},
+};
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[doc(hidden)]
 pub enum TagType {
     #[default]
     Open,
@@ -14,10 +21,12 @@ pub enum TagType {
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
+#[doc(hidden)]
 pub struct Tag {
     pub r#type: TagType,
     pub ansi: Ansi,
-    data: [String; 3],
+    data: [String; 4],
+    attributes: Vec<(String, String)>,
 }
 
 impl Default for Tag {
@@ -25,7 +34,8 @@ impl Default for Tag {
         Self {
             r#type: TagType::SelfClose,
             ansi: Ansi::new(),
-            data: [const { String::new() }; 3],
+            data: [const { String::new() }; 4],
+            attributes: Vec::new(),
         }
     }
 }
@@ -37,6 +47,54 @@ impl Tag {
         tag
     }
 
+    /// Creates an open tag named `name`.
+    pub fn open(name: &str) -> Self {
+        let mut tag = Tag::with_name(name);
+        tag.r#type = TagType::Open;
+        tag.apply_name_styles();
+        tag
+    }
+
+    /// Parses a single tag such as `<c fg="red" b>`.
+    pub fn parse(source: &str) -> Result<Self> {
+        let span = Span::calculate(source);
+        TagParser::new(false).parse(Fragment::new(FragmentType::Tag, source, span))
+    }
+
+    /// Applies the styles implied by the name of this tag, e.g. bold for `<b>`.
+    pub(crate) fn apply_name_styles(&mut self) {
+        match self.name().to_lowercase().as_str() {
+            "b" | "strong" | "th" => {
+                self.set_brightness(DuoEffect::A);
+            }
+            "d" | "dim" => {
+                self.set_brightness(DuoEffect::B);
+            }
+            "i" | "em" => {
+                self.set_italics(Effect::Apply);
+            }
+            "u" | "ins" => {
+                self.set_under(DuoEffect::A);
+            }
+            "uu" => {
+                self.set_under(DuoEffect::B);
+            }
+            "k" | "blink" => {
+                self.set_blink(Effect::Apply);
+            }
+            "r" => {
+                self.set_negative(Effect::Apply);
+            }
+            "h" => {
+                self.set_hidden(Effect::Apply);
+            }
+            "s" | "del" => {
+                self.set_strike(Effect::Apply);
+            }
+            _ => {}
+        }
+    }
+
     pub fn close(&self) -> Self {
         assert!(self.r#type == TagType::Open);
         let mut tag = Tag {
@@ -76,6 +134,33 @@ impl Tag {
     pub fn reset_styles(&mut self) {
         self.ansi = Ansi::new();
     }
+
+    /// Returns the value of the layout attribute `name`, e.g. `width`.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the layout attribute `name` to `value`.
+    pub fn set_attribute(&mut self, name: &str, value: String) {
+        match self.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old)) => *old = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+    }
+
+    /// Returns all layout attributes.
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// Parses the layout attribute `name` as a number.
+    pub(crate) fn usize_attribute(&self, name: &str) -> Option<usize> {
+        self.attribute(name)
+            .and_then(|value| value.trim().parse().ok())
+    }
 }
 
 macro_rules! impl_tag {
@@ -98,7 +183,8 @@ macro_rules! impl_tag {
 impl_tag![
     (0, set_name, name),
     (1, set_custom, custom),
-    (2, set_class, class)
+    (2, set_class, class),
+    (3, set_control, control)
 ];
 
 impl Add for Tag {
@@ -109,6 +195,7 @@ impl Add for Tag {
             r#type: rhs.r#type,
             ansi: self.ansi + rhs.ansi,
             data: rhs.data,
+            attributes: rhs.attributes,
         }
     }
 }
@@ -121,6 +208,7 @@ impl Sub for Tag {
             r#type: self.r#type,
             ansi: self.ansi - rhs.ansi,
             data: self.data,
+            attributes: self.attributes,
         }
     }
 }
@@ -164,6 +252,7 @@ impl Debug for Tag {
             .field("src", &self.class())
             .field("custom", &self.custom())
             .field("type", &self.r#type)
+            .field("attributes", &self.attributes)
             .field("ansi", &self.ansi)
             .finish()
     }
@@ -196,7 +285,7 @@ impl Display for Tag {
             } else {
                 f.write_str("\n")
             };
-        } else if matches!(self.name().as_str(), "p") && !self.custom().is_empty() {
+        } else if matches!(self.name().as_str(), "p" | "div") && !self.custom().is_empty() {
             f.write_fmt(format_args!(
                 "{}",
                 " ".repeat(self.custom().parse::<usize>().unwrap_or(0))
@@ -208,7 +297,12 @@ impl Display for Tag {
         if self.name() == "a" {
             match self.r#type {
                 TagType::Open => {
-                    f.write_str("\x1b]8;;")?;
+                    f.write_str("\x1b]8;")?;
+                    if let Some(id) = self.attribute("id") {
+                        f.write_str("id=")?;
+                        f.write_str(id)?;
+                    }
+                    f.write_str(";")?;
                     f.write_str(self.custom())?;
                     f.write_str("\x1b\\")?;
                 }
@@ -219,6 +313,8 @@ impl Display for Tag {
             }
         }
 
+        f.write_str(self.control())?;
+
         Ok(())
     }
 }
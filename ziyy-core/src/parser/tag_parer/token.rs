@@ -48,15 +48,15 @@ pub enum TokenType {
 /// The `Token` struct is used to represent a token in the parsing process.
 /// The `Token` struct is used to represent a token in the parsing process.
 /// The `Token` struct is used to represent a token in the parsing process.
-pub struct Token {
+pub struct Token<'a> {
     pub r#type: TokenType,
-    pub lexeme: String,
-    pub literal: Option<String>,
+    pub lexeme: &'a str,
+    pub literal: Option<&'a str>,
     pub span: Span,
 }
 
-impl Token {
-    pub fn new(r#type: TokenType, lexeme: String, literal: Option<String>, span: Span) -> Self {
+impl<'a> Token<'a> {
+    pub fn new(r#type: TokenType, lexeme: &'a str, literal: Option<&'a str>, span: Span) -> Self {
         Token {
             r#type,
             literal,
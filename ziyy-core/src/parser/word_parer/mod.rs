@@ -6,9 +6,13 @@ use crate::common::Span;
 use crate::error::Error;
 use crate::scanner::GenericScanner;
 use crate::splitter::fragment::Fragment;
+use crate::term::Icons;
 use scanner::Scanner;
-use token::Token;
+use std::borrow::Cow;
+use std::ops::Range;
+use token::{Literal, Token};
 mod scanner;
+mod shortcode;
 mod token;
 
 macro_rules! shrink {
@@ -24,7 +28,10 @@ macro_rules! shrink {
 }
 
 #[doc(hidden)]
-pub struct WordParser;
+#[derive(Clone)]
+pub struct WordParser {
+    icons: Icons,
+}
 
 impl Default for WordParser {
     fn default() -> Self {
@@ -34,90 +41,108 @@ impl Default for WordParser {
 
 impl WordParser {
     pub fn new() -> Self {
-        Self
+        Self { icons: Icons::None }
+    }
+
+    /// Sets how `:name:` shortcodes such as `:check:` are expanded. They are
+    /// left as they are by default.
+    pub fn set_icons(&mut self, icons: Icons) {
+        self.icons = icons;
     }
 
-    pub fn parse(&self, source: Fragment) -> Result<Vec<Chunk>, Error> {
+    pub fn parse(&self, source: Fragment<'_>) -> Result<Vec<Chunk>, Error> {
         let mut scanner = Scanner::new(source);
         let tokens = scanner.scan_tokens();
 
+        let text: Cow<str> = match tokens.as_slice() {
+            [] => return Ok(vec![]),
+            [token] if matches!(token.literal, Literal::Str(_)) => {
+                let Literal::Str(s) = token.literal else {
+                    unreachable!()
+                };
+                Cow::Borrowed(s)
+            }
+            _ => {
+                let mut text = String::with_capacity(tokens.iter().map(Token::len).sum());
+                for token in &tokens {
+                    match token.literal {
+                        Literal::Str(s) => text.push_str(s),
+                        Literal::Char(c) => text.push(c),
+                    }
+                }
+                Cow::Owned(text)
+            }
+        };
+
         let mut chunks = vec![];
+        let push_word = |chunks: &mut Vec<Chunk>, range: Range<usize>| {
+            if !range.is_empty() {
+                chunks.push(Chunk {
+                    data: ChunkData::Word(
+                        shortcode::expand(&text[range.clone()], self.icons).into_owned(),
+                    ),
+                    span: tokens.to_span(range),
+                });
+            }
+        };
+        let next_escape = |i: usize| text[i..].find('\x1b').map_or(text.len(), |n| i + n);
 
         let mut i = 0;
-        let len = tokens.len();
+        let len = text.len();
 
-        loop {
-            if i >= len {
-                break;
+        while i < len {
+            if !text[i..].starts_with("\x1b[") {
+                // Handle normal characters
+                let end = next_escape(if text[i..].starts_with('\x1b') {
+                    i + 1
+                } else {
+                    i
+                });
+                push_word(&mut chunks, i..end);
+                i = end;
+                continue;
             }
 
-            let c = tokens[i].literal;
+            // Handle escape
+            let g = i;
+            let h = i + 2;
 
-            if c == '\x1b' && tokens[i + 1].literal == '[' {
-                let g = i;
-                i += 2;
-                // Handle escape
-                let h = i;
-
-                if !matches!(tokens[i].literal, '\x30'..='\x39' | '\x3b' | '\x40'..='\x7e') {
-                    while i < len && tokens[i].literal != '\x1b' {
-                        i += 1;
-                    }
-                    let word = tokens[g..i].to_string();
-                    chunks.push(Chunk {
-                        data: ChunkData::Word(word),
-                        span: tokens[g..i].to_span(),
-                    });
-
-                    break;
-                }
-
-                while i < len && !matches!(tokens[i].literal, '\x40'..='\x7e') {
-                    i += 1;
-                }
+            if !text[h..].starts_with(|c| matches!(c, '\x30'..='\x39' | '\x3b' | '\x40'..='\x7e')) {
+                let end = next_escape(h);
+                push_word(&mut chunks, g..end);
+                i = end;
+                continue;
+            }
 
-                if tokens[i].literal == 'm' {
-                    // Handle escape sequence
-                    let escape_sequence = tokens[h..i].to_string();
+            let Some(f) = text[h..]
+                .find(|c| matches!(c, '\x40'..='\x7e'))
+                .map(|n| h + n)
+            else {
+                push_word(&mut chunks, h..len);
+                break;
+            };
 
-                    if let Ok(tag) = self.ansi_to_tag(escape_sequence) {
-                        chunks.push(Chunk {
-                            data: ChunkData::Tag(tag),
-                            span: tokens[g..i].to_span(),
-                        });
-                    }
-                } else {
-                    while i < len && tokens[i].literal != '\x1b' {
-                        i += 1;
-                    }
-                    let word = tokens[h..i].to_string();
+            if text[f..].starts_with('m') {
+                // Handle escape sequence
+                if let Ok(tag) = Self::ansi_to_tag(&text[h..f]) {
                     chunks.push(Chunk {
-                        data: ChunkData::Word(word),
-                        span: tokens[h..i].to_span(),
+                        data: ChunkData::Tag(tag),
+                        span: tokens.to_span(g..f + 1),
                     });
                 }
-                i += 1;
-
-                continue;
+                i = f + 1;
             } else {
-                let h = i;
-                while i < len && tokens[i].literal != '\x1b' {
-                    i += 1;
-                }
-                let word = tokens[h..i].to_string();
-                chunks.push(Chunk {
-                    data: ChunkData::Word(word),
-                    span: tokens[h..i].to_span(),
-                })
+                let end = next_escape(f);
+                push_word(&mut chunks, h..end);
+                i = end;
             }
-            // Handle normal character
-            // i += 1
         }
 
         Ok(chunks)
     }
 
-    fn ansi_to_tag(&self, source: String) -> Result<Tag, i8> {
+    /// Converts the parameters of an SGR escape sequence to a tag.
+    pub(crate) fn ansi_to_tag(source: &str) -> Result<Tag, i8> {
         // Convert ANSI escape codes to tags
         let parts = source.split(';');
 
@@ -272,31 +297,36 @@ impl WordParser {
 }
 
 trait Transform {
-    fn to_string(&self) -> String;
-    fn to_span(&self) -> Span;
+    fn to_span(&self, range: Range<usize>) -> Span;
 }
 
-impl Transform for [Token] {
-    fn to_string(&self) -> String {
-        let mut text = String::with_capacity(self.len());
-
-        for token in self {
-            text.push(token.literal)
-        }
-
-        text
-    }
-
-    fn to_span(&self) -> Span {
-        let mut span = Span::inserted();
-        for token in self {
-            if span == Span::inserted() {
-                span = token.span;
-            } else {
-                span += token.span;
+impl Transform for [Token<'_>] {
+    /// Returns the span in source of a byte range of the decoded text.
+    fn to_span(&self, range: Range<usize>) -> Span {
+        let position = |i: usize, end: bool| {
+            let mut offset = 0;
+            for token in self {
+                let len = token.len();
+                if i < offset + len || (end && i == offset + len) {
+                    return match token.literal {
+                        Literal::Str(s) => {
+                            let mut pos = token.span.start();
+                            for c in s[..i - offset].chars() {
+                                pos += c;
+                            }
+                            pos
+                        }
+                        Literal::Char(_) if end && i > offset => token.span.end(),
+                        Literal::Char(_) => token.span.start(),
+                    };
+                }
+                offset += len;
             }
-        }
+            self.last()
+                .map(|token| token.span.end())
+                .unwrap_or_default()
+        };
 
-        span
+        Span::new(position(range.start, false), position(range.end, true))
     }
 }
@@ -1,23 +1,23 @@
-use super::token::Token;
+use super::token::{Literal, Token};
 use crate::common::Span;
-use crate::scanner::{GenericScanner, Source};
+use crate::scanner::GenericScanner;
 use crate::splitter::fragment::Fragment;
 
-pub struct Scanner {
-    source: Vec<char>,
-    tokens: Vec<Token>,
+pub struct Scanner<'a> {
+    source: &'a str,
+    tokens: Vec<Token<'a>>,
     start: usize,
     current: usize,
     span: Span,
 }
 
-impl Scanner {
-    pub fn new(mut source: Fragment) -> Self {
+impl<'a> Scanner<'a> {
+    pub fn new(mut source: Fragment<'a>) -> Self {
         source.span.tie_start();
 
         Self {
-            source: source.lexeme.chars().collect(),
-            tokens: vec![],
+            source: source.lexeme,
+            tokens: Vec::with_capacity(4),
             start: 0,
             current: 0,
             span: source.span,
@@ -85,12 +85,22 @@ impl Scanner {
         };
     }
 
-    fn text(&self) -> String {
-        self.source[self.start..self.current].to_string()
+    fn text(&self) -> &'a str {
+        &self.source[self.start..self.current]
+    }
+
+    fn run(&mut self) {
+        while !matches!(self.peek(), '\\' | '\x1b') && !self.is_at_end() {
+            self.advance();
+        }
+        self.tokens
+            .push(Token::new(Literal::Str(self.text()), self.span));
+        self.span.tie_end();
     }
 
     fn add_token(&mut self, literal: char) {
-        self.tokens.push(Token::new(literal, self.span));
+        self.tokens
+            .push(Token::new(Literal::Char(literal), self.span));
         self.span.tie_end();
     }
 }
@@ -102,22 +112,6 @@ impl_generic_scanner!(|s: &mut Scanner| {
         '\x1b' => {
             s.add_token('\x1b');
         }
-        _ => s.add_token(c),
+        _ => s.run(),
     }
 });
-
-trait ToString {
-    fn to_string(&self) -> String;
-}
-
-impl ToString for [char] {
-    fn to_string(&self) -> String {
-        let mut text = String::with_capacity(self.len());
-
-        for ch in self {
-            text.push(*ch)
-        }
-
-        text
-    }
-}
@@ -0,0 +1,116 @@
+//! `:name:` shortcodes for emoji and Nerd Font icons.
+
+use std::borrow::Cow;
+
+use crate::term::Icons;
+
+/// Shortcodes with their glyph and ASCII fallback, sorted by name. Nerd Font
+/// icons are named like in its cheat sheet, such as `nf-fa-check`.
+const SHORTCODES: &[(&str, &str, &str)] = &[
+    ("arrow_down", "↓", "v"),
+    ("arrow_left", "←", "<-"),
+    ("arrow_right", "→", "->"),
+    ("arrow_up", "↑", "^"),
+    ("bug", "🐛", "[bug]"),
+    ("bulb", "💡", "[tip]"),
+    ("bullet", "•", "*"),
+    ("check", "✔", "[ok]"),
+    ("cross", "✖", "[x]"),
+    ("ellipsis", "…", "..."),
+    ("fire", "🔥", "[!]"),
+    ("gear", "⚙", "[*]"),
+    ("heart", "❤", "<3"),
+    ("heavy_check_mark", "✔", "[ok]"),
+    ("hourglass", "⌛", "[...]"),
+    ("info", "ℹ", "[i]"),
+    ("key", "🔑", "[key]"),
+    ("lock", "🔒", "[locked]"),
+    ("memo", "📝", "[note]"),
+    ("nf-dev-git", "\u{e702}", "[git]"),
+    ("nf-dev-rust", "\u{e7a8}", "[rust]"),
+    ("nf-fa-bug", "\u{f188}", "[bug]"),
+    ("nf-fa-check", "\u{f00c}", "[ok]"),
+    ("nf-fa-clock_o", "\u{f017}", "[time]"),
+    ("nf-fa-cog", "\u{f013}", "[*]"),
+    ("nf-fa-database", "\u{f1c0}", "[db]"),
+    ("nf-fa-download", "\u{f019}", "[down]"),
+    ("nf-fa-file", "\u{f15b}", "[file]"),
+    ("nf-fa-folder", "\u{f07b}", "[dir]"),
+    ("nf-fa-folder_open", "\u{f07c}", "[dir]"),
+    ("nf-fa-github", "\u{f09b}", "[github]"),
+    ("nf-fa-info_circle", "\u{f05a}", "[i]"),
+    ("nf-fa-lock", "\u{f023}", "[locked]"),
+    ("nf-fa-question_circle", "\u{f059}", "[?]"),
+    ("nf-fa-terminal", "\u{f120}", "[>_]"),
+    ("nf-fa-times", "\u{f00d}", "[x]"),
+    ("nf-fa-upload", "\u{f093}", "[up]"),
+    ("nf-fa-warning", "\u{f071}", "[!]"),
+    ("nf-oct-git_branch", "\u{f418}", "[branch]"),
+    ("nf-pl-branch", "\u{e0a0}", "[branch]"),
+    ("package", "📦", "[pkg]"),
+    ("question", "❓", "[?]"),
+    ("rocket", "🚀", ">>"),
+    ("sparkles", "✨", "*"),
+    ("star", "★", "*"),
+    ("tada", "🎉", "\\o/"),
+    ("unlock", "🔓", "[unlocked]"),
+    ("warning", "⚠", "[!]"),
+    ("white_check_mark", "✅", "[ok]"),
+    ("x", "✖", "[x]"),
+    ("zap", "⚡", "[!]"),
+];
+
+/// Returns the glyph or fallback of the shortcode `name`.
+fn lookup(name: &str, icons: Icons) -> Option<&'static str> {
+    let i = SHORTCODES.binary_search_by(|(n, ..)| n.cmp(&name)).ok()?;
+    let (_, glyph, ascii) = SHORTCODES[i];
+    match icons {
+        Icons::None => None,
+        Icons::Unicode => Some(glyph),
+        Icons::Ascii => Some(ascii),
+    }
+}
+
+/// Replaces the known shortcodes of `text` which stand apart from letters
+/// and digits. Unknown ones, such as the `:30:` of a time, and ones inside
+/// words, such as the `:x:` of `root:x:0`, are left as they are.
+pub(super) fn expand(text: &str, icons: Icons) -> Cow<'_, str> {
+    if icons == Icons::None || !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::new();
+    // start of the text not yet written to `out`
+    let mut done = 0;
+    let mut rest = 0;
+    while let Some(start) = text[rest..].find(':').map(|n| rest + n) {
+        let Some(end) = text[start + 1..].find(':').map(|n| start + 1 + n) else {
+            break;
+        };
+        let name = &text[start + 1..end];
+        let is_name = name
+            .bytes()
+            .all(|c| matches!(c, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'+'));
+        let apart = |c: Option<char>| !c.is_some_and(char::is_alphanumeric);
+        let is_apart =
+            apart(text[..start].chars().next_back()) && apart(text[end + 1..].chars().next());
+        match lookup(name, icons).filter(|_| is_name && is_apart) {
+            Some(glyph) => {
+                out.push_str(&text[done..start]);
+                out.push_str(glyph);
+                done = end + 1;
+                rest = end + 1;
+            }
+            // the closing colon may open the next shortcode
+            None => rest = end,
+        }
+    }
+
+    match done {
+        0 => Cow::Borrowed(text),
+        _ => {
+            out.push_str(&text[done..]);
+            Cow::Owned(out)
+        }
+    }
+}
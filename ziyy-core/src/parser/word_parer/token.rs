@@ -1,13 +1,29 @@
 use crate::common::Span;
-#[derive(Default, Debug)]
-pub struct Token {
-    pub literal: char,
-    #[allow(dead_code)]
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Literal<'a> {
+    /// A run of text borrowed from the source.
+    Str(&'a str),
+    /// A char decoded from an escape sequence.
+    Char(char),
+}
+
+#[derive(Debug)]
+pub struct Token<'a> {
+    pub literal: Literal<'a>,
     pub span: Span,
 }
 
-impl Token {
-    pub fn new(literal: char, span: Span) -> Self {
+impl<'a> Token<'a> {
+    pub fn new(literal: Literal<'a>, span: Span) -> Self {
         Token { literal, span }
     }
+
+    /// Returns the length in bytes of the decoded text of this token.
+    pub fn len(&self) -> usize {
+        match self.literal {
+            Literal::Str(s) => s.len(),
+            Literal::Char(c) => c.len_utf8(),
+        }
+    }
 }
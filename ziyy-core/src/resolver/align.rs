@@ -0,0 +1,248 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{Align, Atom, Item, break_lines, build_line, close_element, flatten, ws},
+};
+use crate::{
+    parser::tag_parer::tag::Tag,
+    width::{display_width, grapheme_width, graphemes},
+};
+
+/// Where the text cut off by truncation is taken from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Truncate {
+    Start,
+    Middle,
+    End,
+}
+
+impl Truncate {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "start" => Some(Truncate::Start),
+            "middle" => Some(Truncate::Middle),
+            "end" => Some(Truncate::End),
+            _ => None,
+        }
+    }
+}
+
+/// Returns whether `tag` is laid out by [`Resolver::set_aligned`] instead of
+/// being wrapped.
+pub(super) fn is_aligned(tag: &Tag) -> bool {
+    match tag.name().as_str() {
+        "p" | "div" => ["align", "min-width", "max-width", "truncate"]
+            .iter()
+            .any(|name| tag.attribute(name).is_some()),
+        "span" => ["align", "width", "min-width", "max-width", "truncate"]
+            .iter()
+            .any(|name| tag.attribute(name).is_some()),
+        _ => false,
+    }
+}
+
+impl Resolver {
+    /// Aligns, pads and truncates the lines of a `p`, `div` or `span`.
+    ///
+    /// The lines of a block are as wide as its `width`, or its widest line,
+    /// or the resolver width when it is centered or aligned to the right,
+    /// limited by `min-width` and `max-width`. Blocks are wrapped to that
+    /// width unless they have a `truncate` of `start`, `middle` or `end`,
+    /// and only padded to its full width when one is given. A `span` is
+    /// always padded to its width and truncated at the `end` by default.
+    pub(super) fn set_aligned(&self, node: &Rc<Node>) {
+        let (inline, indent, hang, align, truncate, widths) = {
+            let chunk = node.chunk().borrow();
+            let tag = chunk.tag().unwrap();
+            let inline = tag.name() == "span";
+            let indent = if inline {
+                0
+            } else {
+                tag.custom().parse().unwrap_or(0)
+            };
+            // widths of blocks include their indent
+            let widths = ["width", "min-width", "max-width"]
+                .map(|name| tag.usize_attribute(name).map(|w| w.saturating_sub(indent)));
+            (
+                inline,
+                indent,
+                tag.usize_attribute("hang").unwrap_or(0),
+                tag.attribute("align").and_then(Align::parse),
+                tag.attribute("truncate").and_then(Truncate::parse),
+                widths,
+            )
+        };
+        let [width, min_width, max_width] = widths;
+        let align = align.unwrap_or(Align::Left);
+
+        let mut atoms = Vec::new();
+        flatten(node, &mut Vec::new(), &mut atoms);
+        let natural = break_lines(&atoms, None, 0);
+        let widest = natural.iter().map(|line| line_width(line)).max();
+
+        let available = match inline {
+            true => None,
+            false => self.width.map(|width| width.saturating_sub(indent)),
+        };
+        let target = match width {
+            Some(width) => width,
+            None if !inline && align != Align::Left => available.or(widest).unwrap_or(0),
+            None => widest.unwrap_or(0),
+        }
+        .max(min_width.unwrap_or(0))
+        .min(max_width.unwrap_or(usize::MAX))
+        .min(available.unwrap_or(usize::MAX));
+
+        let lines = match (truncate, inline) {
+            (Some(truncate), _) => natural
+                .iter()
+                .map(|line| truncated(line, target, truncate))
+                .collect(),
+            (None, true) => natural
+                .iter()
+                .map(|line| truncated(line, target, Truncate::End))
+                .collect(),
+            (None, false) => break_lines(&atoms, Some(target), hang),
+        };
+        let fill_after = inline || width.is_some() || min_width.is_some();
+
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                node.append(ws("\n".to_string()));
+                // the first line is indented by the tag itself
+                if indent > 0 {
+                    node.append(ws(" ".repeat(indent)));
+                }
+            }
+
+            let fill = target.saturating_sub(line_width(line));
+            let (before, after) = match align {
+                Align::Left => (0, fill),
+                Align::Right => (fill, 0),
+                Align::Center => (fill / 2, fill - fill / 2),
+            };
+            if before > 0 {
+                node.append(ws(" ".repeat(before)));
+            }
+            build_line(node, line);
+            if fill_after && after > 0 {
+                node.append(ws(" ".repeat(after)));
+            }
+        }
+        close_element(node);
+    }
+}
+
+fn line_width(line: &[Atom]) -> usize {
+    line.iter().map(Atom::width).sum()
+}
+
+/// Cuts `line` down to `width` columns, replacing the text cut off with an
+/// ellipsis.
+fn truncated(line: &[Atom], width: usize, truncate: Truncate) -> Vec<Atom> {
+    if line_width(line) <= width {
+        return line.to_vec();
+    }
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let room = width - 1;
+    let (front, back) = match truncate {
+        Truncate::Start => (0, room),
+        Truncate::Middle => (room.div_ceil(2), room / 2),
+        Truncate::End => (room, 0),
+    };
+    let mut front = take(line, front, false);
+    let mut back = take(line, back, true);
+    while front
+        .last()
+        .is_some_and(|atom| matches!(atom.item, Item::Space(_)))
+    {
+        front.pop();
+    }
+    while back
+        .first()
+        .is_some_and(|atom| matches!(atom.item, Item::Space(_)))
+    {
+        back.remove(0);
+    }
+
+    // the ellipsis is styled like the text it replaces
+    let chain = front
+        .last()
+        .or(back.first())
+        .map(|atom| atom.chain.clone())
+        .unwrap_or_default();
+    front.push(Atom {
+        chain,
+        item: Item::Word("…".to_string()),
+    });
+    front.append(&mut back);
+    front
+}
+
+/// Returns the atoms of `line` which fit into `width` columns, counting from
+/// the end of the line when `back` is set.
+fn take(line: &[Atom], width: usize, back: bool) -> Vec<Atom> {
+    let mut taken = Vec::new();
+    let mut room = width;
+    let atoms: Vec<&Atom> = match back {
+        true => line.iter().rev().collect(),
+        false => line.iter().collect(),
+    };
+
+    for atom in atoms {
+        let text = match &atom.item {
+            Item::Word(s) | Item::Space(s) => s,
+            _ => {
+                taken.push(atom.clone());
+                continue;
+            }
+        };
+        let len = display_width(text);
+        if len <= room {
+            taken.push(atom.clone());
+            room -= len;
+            continue;
+        }
+
+        let mut parts: Vec<&str> = graphemes(text).collect();
+        if back {
+            parts.reverse();
+        }
+        let mut kept = Vec::new();
+        for part in parts {
+            let len = grapheme_width(part);
+            if len > room {
+                break;
+            }
+            kept.push(part);
+            room -= len;
+        }
+        if back {
+            kept.reverse();
+        }
+        if !kept.is_empty() {
+            let text = kept.concat();
+            taken.push(Atom {
+                chain: atom.chain.clone(),
+                item: match atom.item {
+                    Item::Space(_) => Item::Space(text),
+                    _ => Item::Word(text),
+                },
+            });
+        }
+        break;
+    }
+
+    if back {
+        taken.reverse();
+    }
+    taken
+}
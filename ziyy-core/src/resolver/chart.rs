@@ -0,0 +1,163 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{close_element, word},
+    widget::fill,
+};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        color::Color,
+        tag_parer::tag::{Tag, TagType},
+    },
+};
+
+/// Glyphs of a sparkline, from the lowest value to the highest.
+const LEVELS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+impl Resolver {
+    /// Draws a sparkline of the numbers in `data`, separated by commas or
+    /// whitespace, with one cell for each of the last `width` of them.
+    ///
+    /// The lowest cell stands for `min` and the highest for `max`, which
+    /// default to the smallest and largest number shown. Cells are colored
+    /// by `thresholds`.
+    pub(super) fn set_sparkline(&self, node: &Rc<Node>) {
+        let (mut data, min, max, width, thresholds) = {
+            let mut chunk = node.chunk().borrow_mut();
+            let tag = chunk.tag_mut().unwrap();
+            tag.r#type = TagType::Open;
+            let data: Vec<f64> = tag
+                .attribute("data")
+                .unwrap_or_default()
+                .split(|c: char| c == ',' || c.is_whitespace() || matches!(c, '[' | ']'))
+                .filter_map(|s| s.parse::<f64>().ok())
+                .filter(|n| n.is_finite())
+                .collect();
+            (
+                data,
+                number(tag, "min"),
+                number(tag, "max"),
+                tag.usize_attribute("width"),
+                thresholds(tag),
+            )
+        };
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+
+        if let Some(width) = width {
+            data.drain(..data.len().saturating_sub(width));
+        }
+        let min = min.unwrap_or_else(|| data.iter().copied().fold(f64::INFINITY, f64::min));
+        let max = max.unwrap_or_else(|| data.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+        let cells = data.iter().map(|&value| {
+            let level = match max > min {
+                true => ((value - min) / (max - min) * 7.0).round().clamp(0.0, 7.0) as usize,
+                // a flat line is drawn half way up
+                false => 3,
+            };
+            (LEVELS[level], color(&thresholds, value))
+        });
+
+        // cells of the same color are drawn together
+        let mut run: Vec<&str> = Vec::new();
+        let mut last = None;
+        for (cell, color) in cells {
+            if !run.is_empty() && color != last {
+                append(node, run.concat(), last.take());
+                run.clear();
+            }
+            run.push(cell);
+            last = color;
+        }
+        if !run.is_empty() {
+            append(node, run.concat(), last);
+        }
+        close_element(node);
+    }
+
+    /// Draws a bar of `width` cells filled to `value` out of `max`, colored by
+    /// `thresholds`. The rest of the bar is left blank, so that bars one
+    /// above the other line up.
+    pub(super) fn set_bar(&self, node: &Rc<Node>) {
+        let (value, max, width, thresholds) = {
+            let mut chunk = node.chunk().borrow_mut();
+            let tag = chunk.tag_mut().unwrap();
+            tag.r#type = TagType::Open;
+            (
+                number(tag, "value").unwrap_or(0.0),
+                number(tag, "max").filter(|&n| n > 0.0).unwrap_or(100.0),
+                self.bar_width(tag.usize_attribute("width")),
+                thresholds(tag),
+            )
+        };
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+
+        let cells = fill((value / max).clamp(0.0, 1.0), width);
+        let filled = cells.len();
+        if filled > 0 {
+            append(node, cells.concat(), color(&thresholds, value));
+        }
+        if width > filled {
+            node.append(word(" ".repeat(width - filled)));
+        }
+        close_element(node);
+    }
+}
+
+fn number(tag: &Tag, name: &str) -> Option<f64> {
+    tag.attribute(name)
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|n| n.is_finite())
+}
+
+/// Reads `thresholds` such as `60:yellow; 90:red`, where each value takes the
+/// color of the highest threshold it reaches.
+fn thresholds(tag: &Tag) -> Vec<(f64, Color)> {
+    let mut thresholds: Vec<(f64, Color)> = tag
+        .attribute("thresholds")
+        .unwrap_or_default()
+        .split(';')
+        .filter_map(|s| {
+            let (value, color) = s.split_once(':')?;
+            let color = format!("f{}", color.trim());
+            Some((
+                value.trim().parse::<f64>().ok()?,
+                Color::try_from((color, Span::inserted())).ok()?,
+            ))
+        })
+        .collect();
+    thresholds.sort_by(|a, b| a.0.total_cmp(&b.0));
+    thresholds
+}
+
+fn color(thresholds: &[(f64, Color)], value: f64) -> Option<Color> {
+    thresholds
+        .iter()
+        .rev()
+        .find(|(threshold, _)| value >= *threshold)
+        .map(|(_, color)| color.clone())
+}
+
+/// Appends `text`, in `color` if any.
+fn append(node: &Rc<Node>, text: String, color: Option<Color>) {
+    let Some(color) = color else {
+        node.append(word(text));
+        return;
+    };
+    let mut tag = Tag::with_name("c");
+    tag.r#type = TagType::Open;
+    tag.set_fg_color(color);
+    let element = node.append(Chunk {
+        data: ChunkData::Tag(tag),
+        span: Span::inserted(),
+    });
+    element.append(word(text));
+    close_element(&element);
+}
@@ -0,0 +1,114 @@
+use std::rc::Rc;
+
+use super::{Resolver, document::Node};
+use crate::parser::{chunk::ChunkData, tag_parer::tag::TagType};
+
+impl Resolver {
+    /// Turns a `cursor`, `erase`, `title`, `bell` or `screen` element into
+    /// the control sequence it stands for, which is written in place of the
+    /// element and dropped along with the styles when they are stripped, or
+    /// when the resolver writes no control sequences.
+    ///
+    /// - `cursor` moves `up`, `down`, `left` or `right` by a number of cells,
+    ///   to a `col` or to a `to="row,col"` position, and can `save` or
+    ///   `restore` its position and be made `visible="false"`. Attributes
+    ///   apply in the order they are given.
+    /// - `erase` clears the `line` or the `screen`, either `all` of it or
+    ///   from the cursor to its `start` or `end`.
+    /// - `title` sets the window title to its text, and the icon name as well
+    ///   with `icon`.
+    /// - `screen` switches to the `alt` screen or back to the `main` one.
+    pub(super) fn set_control(&self, node: &Rc<Node>) {
+        let text = {
+            let chunk = node.chunk().borrow();
+            let tag = chunk.tag().unwrap();
+            let mut text = String::new();
+            if tag.name() == "title" {
+                text = node
+                    .descendants()
+                    .skip(1)
+                    .filter_map(|child| match &child.chunk().borrow().data {
+                        ChunkData::Word(s) | ChunkData::WhiteSpace(s) => Some(s.clone()),
+                        ChunkData::Tag(_) => None,
+                    })
+                    .collect::<String>()
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                // the title must not end the sequence early
+                text.retain(|c| !c.is_control());
+            }
+            text
+        };
+
+        let mut chunk = node.chunk().borrow_mut();
+        let tag = chunk.tag_mut().unwrap();
+        let mut control = String::new();
+        let number = |value: &str| value.trim().parse::<usize>().unwrap_or(1);
+        match tag.name().as_str() {
+            "cursor" => {
+                for (name, value) in tag.attributes() {
+                    match name.as_str() {
+                        "up" => control.push_str(&format!("\x1b[{}A", number(value))),
+                        "down" => control.push_str(&format!("\x1b[{}B", number(value))),
+                        "right" => control.push_str(&format!("\x1b[{}C", number(value))),
+                        "left" => control.push_str(&format!("\x1b[{}D", number(value))),
+                        "col" => control.push_str(&format!("\x1b[{}G", number(value))),
+                        "to" => {
+                            let mut position = value.split([',', ' ']).filter(|s| !s.is_empty());
+                            let row = position.next().map_or(1, number);
+                            let col = position.next().map_or(1, number);
+                            control.push_str(&format!("\x1b[{row};{col}H"));
+                        }
+                        "save" => control.push_str("\x1b7"),
+                        "restore" => control.push_str("\x1b8"),
+                        "visible" if value == "false" => control.push_str("\x1b[?25l"),
+                        "visible" => control.push_str("\x1b[?25h"),
+                        _ => {}
+                    }
+                }
+            }
+            "erase" => {
+                for (name, value) in tag.attributes() {
+                    let n = match value.as_str() {
+                        "end" => 0,
+                        "start" => 1,
+                        _ => 2,
+                    };
+                    match name.as_str() {
+                        "line" => control.push_str(&format!("\x1b[{n}K")),
+                        "screen" => control.push_str(&format!("\x1b[{n}J")),
+                        _ => {}
+                    }
+                }
+            }
+            "title" => {
+                let n = match tag.attribute("icon") {
+                    Some(_) => 0,
+                    None => 2,
+                };
+                control = format!("\x1b]{n};{text}\x07");
+            }
+            "bell" => control.push('\x07'),
+            "screen" => {
+                if tag.attribute("alt").is_some() {
+                    control.push_str("\x1b[?1049h");
+                }
+                if tag.attribute("main").is_some() {
+                    control.push_str("\x1b[?1049l");
+                }
+            }
+            _ => {}
+        }
+        if !self.controls {
+            control.clear();
+        }
+        tag.set_control(control);
+        tag.r#type = TagType::SelfClose;
+        drop(chunk);
+
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+    }
+}
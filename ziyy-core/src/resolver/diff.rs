@@ -0,0 +1,169 @@
+use std::ops::Range;
+
+use super::highlight::Token;
+
+/// Splits unified-diff `text` into tokens of the classes `meta`, `hunk`,
+/// `added`, `removed` and `context`, one per line.
+///
+/// With `words`, every removed line that is followed by an added line
+/// replacing it also gets `removed.word` and `added.word` tokens for the
+/// words which differ between the two.
+pub(super) fn unified(text: &str, words: bool) -> Vec<Token> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches('\n').trim_end_matches('\r');
+        lines.push((start..start + content.len(), content));
+        start += line.len();
+    }
+
+    let mut classes = Vec::with_capacity(lines.len());
+    for (i, (_, line)) in lines.iter().enumerate() {
+        let next = lines.get(i + 1).map_or("", |(_, line)| line);
+        let class = if ["diff ", "index ", "\\"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+            || line.starts_with("--- ") && next.starts_with("+++ ")
+            || line.starts_with("+++ ") && classes.last() == Some(&"meta")
+        {
+            "meta"
+        } else if line.starts_with("@@") {
+            "hunk"
+        } else if line.starts_with('+') {
+            "added"
+        } else if line.starts_with('-') {
+            "removed"
+        } else {
+            "context"
+        };
+        classes.push(class);
+    }
+
+    let mut nested = vec![Vec::new(); lines.len()];
+    if words {
+        let mut i = 0;
+        while i < lines.len() {
+            let removed = classes[i..]
+                .iter()
+                .take_while(|&&class| class == "removed")
+                .count();
+            let added = classes[i + removed..]
+                .iter()
+                .take_while(|&&class| class == "added")
+                .count();
+            // the n-th removed line is taken to be replaced by the n-th
+            // added line
+            for n in 0..removed.min(added) {
+                let (old, new) = (i + n, i + removed + n);
+                let (old_words, new_words) =
+                    changes(&lines[old].0, lines[old].1, &lines[new].0, lines[new].1);
+                nested[old] = old_words
+                    .into_iter()
+                    .map(|range| (range, "removed.word"))
+                    .collect();
+                nested[new] = new_words
+                    .into_iter()
+                    .map(|range| (range, "added.word"))
+                    .collect();
+            }
+            i += (removed + added).max(1);
+        }
+    }
+
+    let mut tokens = Vec::new();
+    for (((range, _), class), words) in lines.into_iter().zip(classes).zip(nested) {
+        if !range.is_empty() {
+            tokens.push((range, class));
+            tokens.extend(words);
+        }
+    }
+    tokens
+}
+
+/// Returns the ranges of the words of `old` and `new`, both without their
+/// leading `-` or `+`, which are not part of the other line.
+fn changes(
+    old_range: &Range<usize>,
+    old: &str,
+    new_range: &Range<usize>,
+    new: &str,
+) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_words = split_words(&old[1..], old_range.start + 1);
+    let new_words = split_words(&new[1..], new_range.start + 1);
+    let (old_kept, new_kept) = common(&old_words, &new_words);
+    (
+        changed(&old_words, &old_kept),
+        changed(&new_words, &new_kept),
+    )
+}
+
+/// Splits `line` into words, runs of whitespace and single punctuation
+/// characters, with their ranges starting at `offset`.
+fn split_words(line: &str, offset: usize) -> Vec<(Range<usize>, &str)> {
+    let kind = |c: char| match c {
+        c if c.is_alphanumeric() || c == '_' => 0,
+        c if c.is_whitespace() => 1,
+        _ => 2,
+    };
+    let mut words: Vec<(Range<usize>, &str)> = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let end = match chars.peek() {
+            Some(&(i, next)) if kind(c) == 2 || kind(next) != kind(c) => i,
+            Some(_) => continue,
+            None => line.len(),
+        };
+        words.push((offset + start..offset + end, &line[start..end]));
+        start = end;
+    }
+    words
+}
+
+/// Returns which words of `old` and `new` belong to their longest common
+/// subsequence.
+fn common(old: &[(Range<usize>, &str)], new: &[(Range<usize>, &str)]) -> (Vec<bool>, Vec<bool>) {
+    let (n, m) = (old.len(), new.len());
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = match old[i].1 == new[j].1 {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut old_kept, mut new_kept) = (vec![false; n], vec![false; m]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i].1 == new[j].1 {
+            old_kept[i] = true;
+            new_kept[j] = true;
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (old_kept, new_kept)
+}
+
+/// Merges the words which are not `kept` into ranges. Changes separated by
+/// nothing but one run of whitespace are merged as well.
+fn changed(words: &[(Range<usize>, &str)], kept: &[bool]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (i, (range, _)) in words.iter().enumerate() {
+        if kept[i] {
+            continue;
+        }
+        let bridged = i >= 2 && !kept[i - 2] && words[i - 1].1.chars().all(char::is_whitespace);
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start || bridged => last.end = range.end,
+            _ => ranges.push(range.clone()),
+        }
+    }
+    ranges
+}
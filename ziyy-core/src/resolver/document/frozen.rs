@@ -0,0 +1,291 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Debug, Display, Formatter},
+    ptr,
+    rc::Rc,
+};
+
+use super::{
+    Document, Node,
+    display::Indentation,
+    iter::{Ancestors, Children, Descendants, Edge, NextSiblings, PrevSiblings, Traverse, Tree},
+    node::Kin,
+};
+use crate::parser::chunk::Chunk;
+
+/// An immutable copy of a [`Document`] which can be shared between threads.
+///
+/// Nodes are stored in an arena and linked by id, so a template can be
+/// resolved once, stored in a `static` and rendered from many threads.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use std::sync::Arc;
+/// use ziyy::{ColorLevel, FrozenDocument, resolve};
+///
+/// let doc = resolve("<b>bold</b>", ColorLevel::TrueColor).unwrap();
+/// let frozen = Arc::new(doc.freeze());
+///
+/// let shared = frozen.clone();
+/// let rendered = std::thread::spawn(move || shared.render()).join().unwrap();
+/// assert_eq!(rendered, "\x1b[1mbold\x1b[22m");
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct FrozenDocument {
+    nodes: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    kin: Kin,
+    chunk: Chunk,
+}
+
+impl Document {
+    /// Returns an immutable copy of this document which is `Send + Sync`.
+    pub fn freeze(&self) -> FrozenDocument {
+        let nodes = self.nodes.borrow();
+        FrozenDocument {
+            nodes: nodes
+                .iter()
+                .map(|node| Entry {
+                    kin: node.kin.borrow().clone(),
+                    chunk: node.chunk().borrow().clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl FrozenDocument {
+    /// Returns the node with `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a node of this document.
+    pub fn get(&self, id: u32) -> FrozenNode<'_> {
+        assert!((id as usize) < self.nodes.len(), "invalid node id {id}");
+        FrozenNode { doc: self, id }
+    }
+
+    /// Returns the root node.
+    pub fn root(&self) -> FrozenNode<'_> {
+        self.get(0)
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if this document has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Renders this document.
+    pub fn render(&self) -> String {
+        let mut buf = String::new();
+        self.root().to_string(&mut buf);
+        buf
+    }
+
+    /// Returns a [`Document`] with the nodes of this one, which can be
+    /// changed without changing this one.
+    pub fn thaw(&self) -> Rc<Document> {
+        Rc::new_cyclic(|doc| Document {
+            recycled: RefCell::new(Vec::new()),
+            nodes: RefCell::new(
+                self.nodes
+                    .iter()
+                    .zip(0..)
+                    .map(|(entry, id)| {
+                        let node = Node::new(id, entry.chunk.clone(), doc.clone());
+                        *node.kin.borrow_mut() = entry.kin.clone();
+                        Rc::new(node)
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
+
+/// A node of a [`FrozenDocument`].
+#[derive(Clone, Copy)]
+pub struct FrozenNode<'a> {
+    doc: &'a FrozenDocument,
+    id: u32,
+}
+
+impl<'a> FrozenNode<'a> {
+    fn entry(&self) -> &'a Entry {
+        &self.doc.nodes[self.id as usize]
+    }
+
+    fn axis(&self, id: Option<u32>) -> Option<Self> {
+        id.map(|id| FrozenNode { doc: self.doc, id })
+    }
+
+    /// Returns the id of this node.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the document of this node.
+    pub fn doc(&self) -> &'a FrozenDocument {
+        self.doc
+    }
+
+    /// Returns the chunk of this node.
+    pub fn chunk(&self) -> &'a Chunk {
+        &self.entry().chunk
+    }
+
+    /// Returns the parent of this node.
+    pub fn parent(&self) -> Option<Self> {
+        self.axis(self.entry().kin.parent)
+    }
+
+    /// Returns the previous sibling of this node.
+    pub fn prev_sibling(&self) -> Option<Self> {
+        self.axis(self.entry().kin.prev_sibling)
+    }
+
+    /// Returns the next sibling of this node.
+    pub fn next_sibling(&self) -> Option<Self> {
+        self.axis(self.entry().kin.next_sibling)
+    }
+
+    /// Returns the first child of this node.
+    pub fn first_child(&self) -> Option<Self> {
+        self.axis(self.entry().kin.children.map(|(id, _)| id))
+    }
+
+    /// Returns the last child of this node.
+    pub fn last_child(&self) -> Option<Self> {
+        self.axis(self.entry().kin.children.map(|(_, id)| id))
+    }
+
+    /// Returns true if this node has children.
+    pub fn has_children(&self) -> bool {
+        self.entry().kin.children.is_some()
+    }
+
+    /// Returns an iterator over children.
+    pub fn children(&self) -> Children<Self> {
+        Children::new(self)
+    }
+
+    /// Returns an iterator which traverses the subtree starting at this node.
+    pub fn traverse(&self) -> Traverse<Self> {
+        Traverse::new(*self)
+    }
+
+    /// Returns an iterator over this node and its descendants.
+    pub fn descendants(&self) -> Descendants<Self> {
+        Descendants::new(*self)
+    }
+
+    /// Returns an iterator over ancestors.
+    pub fn ancestors(&self) -> Ancestors<Self> {
+        Ancestors(Some(*self))
+    }
+
+    /// Returns an iterator over previous siblings.
+    pub fn prev_siblings(&self) -> PrevSiblings<Self> {
+        PrevSiblings(self.prev_sibling())
+    }
+
+    /// Returns an iterator over next siblings.
+    pub fn next_siblings(&self) -> NextSiblings<Self> {
+        NextSiblings(self.next_sibling())
+    }
+
+    /// Returns the string representation of this node.
+    pub fn to_string(&self, buf: &mut String) {
+        if self.has_children() {
+            let tag = self.chunk().data.tag().unwrap();
+            buf.push_str(tag.to_string().as_str());
+            for child in self.children() {
+                child.to_string(buf);
+            }
+        } else {
+            buf.push_str(self.chunk().data.to_string().as_str());
+        }
+    }
+}
+
+impl Tree for FrozenNode<'_> {
+    fn parent(&self) -> Option<Self> {
+        FrozenNode::parent(self)
+    }
+
+    fn prev_sibling(&self) -> Option<Self> {
+        FrozenNode::prev_sibling(self)
+    }
+
+    fn next_sibling(&self) -> Option<Self> {
+        FrozenNode::next_sibling(self)
+    }
+
+    fn first_child(&self) -> Option<Self> {
+        FrozenNode::first_child(self)
+    }
+
+    fn last_child(&self) -> Option<Self> {
+        FrozenNode::last_child(self)
+    }
+}
+
+impl PartialEq for FrozenNode<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.doc, other.doc) && self.id == other.id
+    }
+}
+
+impl Eq for FrozenNode<'_> {}
+
+impl Debug for FrozenNode<'_> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("FrozenNode")
+            .field("id", &self.id)
+            .field("kin", &self.entry().kin)
+            .field("chunk", self.chunk())
+            .finish()
+    }
+}
+
+impl Debug for FrozenDocument {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("FrozenDocument")
+            .field("nodes", &self.nodes)
+            .finish()
+    }
+}
+
+impl Display for FrozenDocument {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let mut indent: Indentation = Indentation::new(true);
+
+        for edge in self.root().traverse() {
+            match edge {
+                Edge::Open(node) if node.has_children() => {
+                    indent.indent(node.next_sibling().is_some());
+                    writeln!(f, "{indent}{:#}", node.chunk())?;
+                }
+                Edge::Open(node) => {
+                    indent.indent(node.next_sibling().is_some());
+                    writeln!(f, "{indent}{:#}", node.chunk())?;
+                    indent.deindent();
+                }
+                Edge::Close(node) if node.has_children() => {
+                    indent.deindent();
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
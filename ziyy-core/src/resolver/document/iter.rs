@@ -1,24 +1,61 @@
-use std::{ops::Deref, rc::Rc};
+use std::rc::Rc;
 
 use super::Node;
 
-#[derive(Debug)]
-pub struct Children {
-    front: Option<Rc<Node>>,
-    back: Option<Rc<Node>>,
+/// A handle to a node which can be walked by the tree iterators.
+pub trait Tree: Sized + Clone + PartialEq {
+    /// Returns the parent of this node.
+    fn parent(&self) -> Option<Self>;
+    /// Returns the previous sibling of this node.
+    fn prev_sibling(&self) -> Option<Self>;
+    /// Returns the next sibling of this node.
+    fn next_sibling(&self) -> Option<Self>;
+    /// Returns the first child of this node.
+    fn first_child(&self) -> Option<Self>;
+    /// Returns the last child of this node.
+    fn last_child(&self) -> Option<Self>;
 }
 
-impl Clone for Children {
-    fn clone(&self) -> Self {
-        Self {
-            front: self.front.clone(),
-            back: self.back.clone(),
+impl Tree for Rc<Node> {
+    fn parent(&self) -> Option<Self> {
+        Node::parent(self)
+    }
+
+    fn prev_sibling(&self) -> Option<Self> {
+        Node::prev_sibling(self)
+    }
+
+    fn next_sibling(&self) -> Option<Self> {
+        Node::next_sibling(self)
+    }
+
+    fn first_child(&self) -> Option<Self> {
+        Node::first_child(self)
+    }
+
+    fn last_child(&self) -> Option<Self> {
+        Node::last_child(self)
+    }
+}
+
+/// Iterator over children.
+#[derive(Debug, Clone)]
+pub struct Children<N = Rc<Node>> {
+    front: Option<N>,
+    back: Option<N>,
+}
+
+impl<N: Tree> Children<N> {
+    pub(super) fn new(node: &N) -> Self {
+        Children {
+            front: node.first_child(),
+            back: node.last_child(),
         }
     }
 }
 
-impl Iterator for Children {
-    type Item = Rc<Node>;
+impl<N: Tree> Iterator for Children<N> {
+    type Item = N;
     fn next(&mut self) -> Option<Self::Item> {
         if self.front == self.back {
             let node = self.front.take();
@@ -26,10 +63,7 @@ impl Iterator for Children {
             node
         } else {
             let node = self.front.take();
-            self.front = node
-                .as_ref()
-                .map(|x| x.deref())
-                .and_then(Node::next_sibling);
+            self.front = node.as_ref().and_then(N::next_sibling);
             node
         }
     }
@@ -37,16 +71,16 @@ impl Iterator for Children {
 
 /// Open or close edge of a node.
 #[derive(Debug, Clone)]
-pub enum Edge {
+pub enum Edge<N = Rc<Node>> {
     /// Open.
-    Open(Rc<Node>),
+    Open(N),
     /// Close.
-    Close(Rc<Node>),
+    Close(N),
 }
 
-impl Eq for Edge {}
+impl<N: Eq> Eq for Edge<N> {}
 
-impl PartialEq for Edge {
+impl<N: PartialEq> PartialEq for Edge<N> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Edge::Open(a), Edge::Open(b)) | (Edge::Close(a), Edge::Close(b)) => a == b,
@@ -57,13 +91,22 @@ impl PartialEq for Edge {
 
 /// Iterator which traverses a subtree.
 #[derive(Debug, Clone)]
-pub struct Traverse {
-    root: Option<Rc<Node>>,
-    edge: Option<Edge>,
+pub struct Traverse<N = Rc<Node>> {
+    root: Option<N>,
+    edge: Option<Edge<N>>,
 }
 
-impl Iterator for Traverse {
-    type Item = Edge;
+impl<N: Tree> Traverse<N> {
+    pub(super) fn new(root: N) -> Self {
+        Traverse {
+            root: Some(root),
+            edge: None,
+        }
+    }
+}
+
+impl<N: Tree> Iterator for Traverse<N> {
+    type Item = Edge<N>;
     fn next(&mut self) -> Option<Self::Item> {
         match &self.edge {
             None => {
@@ -79,7 +122,7 @@ impl Iterator for Traverse {
                 }
             }
             Some(Edge::Close(node)) => {
-                if *node == self.root.clone().unwrap() {
+                if Some(node) == self.root.as_ref() {
                     self.root = None;
                     self.edge = None;
                 } else if let Some(next_sibling) = node.next_sibling() {
@@ -94,17 +137,17 @@ impl Iterator for Traverse {
 }
 
 /// Iterator over a node and its descendants.
-#[derive(Debug)]
-pub struct Descendants(Traverse);
+#[derive(Debug, Clone)]
+pub struct Descendants<N = Rc<Node>>(Traverse<N>);
 
-impl Clone for Descendants {
-    fn clone(&self) -> Self {
-        Descendants(self.0.clone())
+impl<N: Tree> Descendants<N> {
+    pub(super) fn new(root: N) -> Self {
+        Descendants(Traverse::new(root))
     }
 }
 
-impl Iterator for Descendants {
-    type Item = Rc<Node>;
+impl<N: Tree> Iterator for Descendants<N> {
+    type Item = N;
     fn next(&mut self) -> Option<Self::Item> {
         for edge in &mut self.0 {
             if let Edge::Open(node) = edge {
@@ -119,19 +162,14 @@ macro_rules! axis_iterators {
     ($(#[$m:meta] $i:ident($f:path);)*) => {
         $(
             #[$m]
-            #[derive(Debug)]
-            pub struct $i(Option<Rc<Node>>);
-            impl Clone for $i {
-                fn clone(&self) -> Self {
-                    $i(self.0.clone())
-                }
-            }
+            #[derive(Debug, Clone)]
+            pub struct $i<N = Rc<Node>>(pub(super) Option<N>);
 
-            impl Iterator for $i {
-                type Item = Rc<Node>;
+            impl<N: Tree> Iterator for $i<N> {
+                type Item = N;
                 fn next(&mut self) -> Option<Self::Item> {
                     let node = self.0.take();
-                    self.0 = node.as_ref().map(|x| x.deref()).and_then($f);
+                    self.0 = node.as_ref().and_then($f);
                     node
                 }
             }
@@ -141,19 +179,19 @@ macro_rules! axis_iterators {
 
 axis_iterators! {
     /// Iterator over ancestors.
-    Ancestors(Node::parent);
+    Ancestors(Tree::parent);
 
     /// Iterator over previous siblings.
-    PrevSiblings(Node::prev_sibling);
+    PrevSiblings(Tree::prev_sibling);
 
     /// Iterator over next siblings.
-    NextSiblings(Node::next_sibling);
+    NextSiblings(Tree::next_sibling);
 
     /// Iterator over first children.
-    FirstChildren(Node::first_child);
+    FirstChildren(Tree::first_child);
 
     /// Iterator over last children.
-    LastChildren(Node::last_child);
+    LastChildren(Tree::last_child);
 }
 
 impl Node {
@@ -167,19 +205,36 @@ impl Node {
 
     /// Returns an iterator which traverses the subtree starting at this node.
     pub fn traverse(self: &Rc<Node>) -> Traverse {
-        Traverse {
-            root: Some(self.clone()),
-            edge: None,
-        }
+        Traverse::new(self.clone())
     }
 
     /// Returns an iterator over this node and its descendants.
     pub fn descendants(self: &Rc<Node>) -> Descendants {
-        Descendants(self.traverse())
+        Descendants::new(self.clone())
     }
 
     /// Returns an iterator over ancestors.
     pub fn ancestors(self: &Rc<Node>) -> Ancestors {
         Ancestors(Some(self.clone()))
     }
+
+    /// Returns an iterator over previous siblings.
+    pub fn prev_siblings(&self) -> PrevSiblings {
+        PrevSiblings(self.prev_sibling())
+    }
+
+    /// Returns an iterator over next siblings.
+    pub fn next_siblings(&self) -> NextSiblings {
+        NextSiblings(self.next_sibling())
+    }
+
+    /// Returns an iterator over first children.
+    pub fn first_children(&self) -> FirstChildren {
+        FirstChildren(self.first_child())
+    }
+
+    /// Returns an iterator over last children.
+    pub fn last_children(&self) -> LastChildren {
+        LastChildren(self.last_child())
+    }
 }
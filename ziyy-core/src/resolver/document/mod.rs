@@ -11,11 +11,17 @@ use crate::{
         tag_parer::tag::{Tag, TagType},
     },
 };
+pub use frozen::{FrozenDocument, FrozenNode};
+pub use iter::Edge;
 pub use node::Node;
+pub use select::Selector;
 
 mod display;
+mod frozen;
 mod iter;
+mod mutate;
 mod node;
+mod select;
 
 #[derive(Clone)]
 #[doc(hidden)]
@@ -90,11 +96,14 @@ impl Document {
     pub fn len(&self) -> usize {
         self.nodes.borrow().len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.borrow().is_empty()
+    }
 }
 
 impl Debug for Document {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        use iter::Edge;
         if f.alternate() {
             write!(f, "Document {{")?;
             for edge in self.root().traverse() {
@@ -130,7 +139,6 @@ impl Debug for Document {
 impl Display for Document {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         use display::Indentation;
-        use iter::Edge;
 
         let mut indent: Indentation = Indentation::new(true);
 
@@ -0,0 +1,320 @@
+use std::rc::{Rc, Weak};
+
+use super::{Document, Node, node::RNode};
+use crate::{
+    common::Span,
+    error::{Error, ErrorType, Result},
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::{Tag, TagType},
+    },
+};
+
+macro_rules! invalid {
+    ( $span:expr, $($arg:tt)* ) => {
+        Err(Error::new(ErrorType::InvalidOperation, format!($($arg)*), $span))
+    };
+}
+
+/// Checked changes to an unresolved document.
+///
+/// Unlike the raw id based methods these never corrupt sibling links: every
+/// node involved is checked to be live and part of the same document, tags
+/// are inserted together with their close tag, and removed nodes are never
+/// silently reused by a stale `Rc<Node>`.
+impl Node {
+    /// Returns true if this node is still part of its document.
+    pub fn is_live(&self) -> bool {
+        self.check().is_ok()
+    }
+
+    /// Returns the parent of this node, or a `StaleNode` error if this node
+    /// was removed.
+    pub fn try_parent(&self) -> Result<Option<RNode>> {
+        self.check()?;
+        Ok(self.parent())
+    }
+
+    /// Returns the previous sibling of this node, checked like
+    /// [`try_parent`](Node::try_parent).
+    pub fn try_prev_sibling(&self) -> Result<Option<RNode>> {
+        self.check()?;
+        Ok(self.prev_sibling())
+    }
+
+    /// Returns the next sibling of this node, checked like
+    /// [`try_parent`](Node::try_parent).
+    pub fn try_next_sibling(&self) -> Result<Option<RNode>> {
+        self.check()?;
+        Ok(self.next_sibling())
+    }
+
+    /// Returns the first child of this node, checked like
+    /// [`try_parent`](Node::try_parent).
+    pub fn try_first_child(&self) -> Result<Option<RNode>> {
+        self.check()?;
+        Ok(self.first_child())
+    }
+
+    /// Returns the last child of this node, checked like
+    /// [`try_parent`](Node::try_parent).
+    pub fn try_last_child(&self) -> Result<Option<RNode>> {
+        self.check()?;
+        Ok(self.last_child())
+    }
+
+    /// Appends a new element as the last child of this element.
+    pub fn try_append(&self, tag: Tag) -> Result<RNode> {
+        self.check_container()?;
+        let node = self.new_element(tag)?;
+        match self.close_child() {
+            Some(close) => close.insert_id_before(node.id),
+            None => self.append_id(node.id),
+        };
+        Ok(node)
+    }
+
+    /// Prepends a new element as the first child of this element.
+    pub fn try_prepend(&self, tag: Tag) -> Result<RNode> {
+        self.check_container()?;
+        let node = self.new_element(tag)?;
+        self.prepend_id(node.id);
+        Ok(node)
+    }
+
+    /// Appends text as the last child of this element.
+    pub fn try_append_text(&self, text: &str) -> Result<RNode> {
+        self.check_container()?;
+        let chunk = Chunk {
+            data: ChunkData::Word(text.to_string()),
+            span: Span::inserted(),
+        };
+        Ok(match self.close_child() {
+            Some(close) => close.insert_before(chunk),
+            None => self.append(chunk),
+        })
+    }
+
+    /// Prepends text as the first child of this element.
+    pub fn try_prepend_text(&self, text: &str) -> Result<RNode> {
+        self.check_container()?;
+        Ok(self.prepend(Chunk {
+            data: ChunkData::Word(text.to_string()),
+            span: Span::inserted(),
+        }))
+    }
+
+    /// Inserts a new element before this node.
+    pub fn try_insert_before(&self, tag: Tag) -> Result<RNode> {
+        self.check_sibling()?;
+        let node = self.new_element(tag)?;
+        Ok(self.insert_id_before(node.id))
+    }
+
+    /// Inserts a new element after this node.
+    pub fn try_insert_after(&self, tag: Tag) -> Result<RNode> {
+        self.check_sibling()?;
+        let node = self.new_element(tag)?;
+        Ok(self.insert_id_after(node.id))
+    }
+
+    /// Wraps this node in a new element.
+    pub fn wrap(&self, tag: Tag) -> Result<RNode> {
+        self.wrap_range(self, tag)
+    }
+
+    /// Wraps the siblings from this node to `last` in a new element.
+    pub fn wrap_range(&self, last: &Node, tag: Tag) -> Result<RNode> {
+        self.check_sibling()?;
+        last.check_sibling()?;
+        if !Weak::ptr_eq(&self.doc, &last.doc)
+            || self.parent().map(|node| node.id) != last.parent().map(|node| node.id)
+        {
+            return invalid!(self.span(), "cannot wrap nodes with different parents");
+        }
+        if tag.r#type != TagType::Open {
+            return invalid!(self.span(), "cannot wrap nodes in {:#}", tag);
+        }
+
+        let mut range = vec![self.doc().get(self.id)];
+        if last.id != self.id {
+            let Some(end) = self.next_siblings().find(|node| node.id == last.id) else {
+                return invalid!(
+                    self.span(),
+                    "{} does not follow {}",
+                    last.span(),
+                    self.span()
+                );
+            };
+            range.extend(self.next_siblings().take_while(|node| node.id != last.id));
+            range.push(end);
+        }
+
+        let wrapper = self.doc().orphan(Chunk {
+            data: ChunkData::Tag(tag.clone()),
+            span: Span::inserted(),
+        });
+        self.insert_id_before(wrapper.id);
+        for node in range {
+            node.detach(false);
+            wrapper.append_id(node.id);
+        }
+        wrapper.append(Chunk {
+            data: ChunkData::Tag(tag.close()),
+            span: Span::inserted(),
+        });
+
+        Ok(wrapper)
+    }
+
+    /// Replaces this element with its children.
+    pub fn unwrap_element(&self) -> Result<()> {
+        self.check_sibling()?;
+        if !self.is_element() {
+            return invalid!(self.span(), "only elements can be unwrapped");
+        }
+
+        let close = self.close_child();
+        let children: Vec<_> = self.children().collect();
+        for child in children {
+            if close.as_ref().is_some_and(|close| close.id == child.id) {
+                continue;
+            }
+            child.detach(false);
+            self.insert_id_before(child.id);
+        }
+
+        self.remove()
+    }
+
+    /// Removes this node and its descendants from the document.
+    ///
+    /// Any `Rc<Node>` still pointing into the removed subtree becomes stale.
+    pub fn remove(&self) -> Result<()> {
+        self.check_sibling()?;
+        self.discard();
+        Ok(())
+    }
+
+    /// Sets an attribute of this element as if it was written in its tag,
+    /// e.g. `set_attribute("c", "red")`.
+    pub fn set_attribute(&self, name: &str, value: &str) -> Result<()> {
+        self.check()?;
+        let mut chunk = self.chunk().borrow_mut();
+        let span = chunk.span;
+        let Some(tag) = chunk.tag_mut() else {
+            return invalid!(span, "only tags have attributes");
+        };
+        if tag.r#type == TagType::Close || tag.name().starts_with('$') {
+            return invalid!(span, "cannot set attributes of {:#}", tag);
+        }
+
+        let quote = if !value.contains('"') {
+            '"'
+        } else if !value.contains('\'') {
+            '\''
+        } else {
+            return Err(Error::new(
+                ErrorType::InvalidTagAttributeValue,
+                format!("{value:?} cannot be quoted"),
+                span,
+            ));
+        };
+        let parsed = Tag::parse(&format!("<{} {name}={quote}{value}{quote} />", tag.name()))?;
+
+        if !parsed.custom().is_empty() {
+            tag.set_custom(parsed.custom().clone());
+        }
+        if !parsed.class().is_empty() {
+            tag.set_class(parsed.class().clone());
+        }
+        for (name, value) in parsed.attributes() {
+            tag.set_attribute(name, value.clone());
+        }
+        tag.ansi = tag.ansi.clone() + parsed.ansi;
+        Ok(())
+    }
+
+    fn check(&self) -> Result<()> {
+        let live = self.doc.upgrade().is_some_and(|doc| {
+            doc.nodes
+                .borrow()
+                .get(self.id as usize)
+                .is_some_and(|node| std::ptr::eq(Rc::as_ptr(node), self))
+                && !doc.recycled.borrow().contains(&self.id)
+        });
+
+        if live {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorType::StaleNode,
+                format!("node {} was removed from its document", self.id),
+                self.span(),
+            ))
+        }
+    }
+
+    fn check_container(&self) -> Result<()> {
+        self.check()?;
+        if self.id == 0 || self.is_element() {
+            Ok(())
+        } else {
+            invalid!(self.span(), "only elements can have children")
+        }
+    }
+
+    fn check_sibling(&self) -> Result<()> {
+        self.check()?;
+        if self.parent().is_none() {
+            return invalid!(self.span(), "node has no parent");
+        }
+        if self.is_close() {
+            return invalid!(self.span(), "cannot move a close tag");
+        }
+        Ok(())
+    }
+
+    fn is_element(&self) -> bool {
+        self.chunk()
+            .borrow()
+            .is_tag_and(|tag| tag.r#type == TagType::Open)
+    }
+
+    fn is_close(&self) -> bool {
+        self.chunk()
+            .borrow()
+            .is_tag_and(|tag| tag.r#type == TagType::Close)
+    }
+
+    fn close_child(&self) -> Option<RNode> {
+        self.last_child().filter(|last| last.is_close())
+    }
+
+    fn span(&self) -> Span {
+        self.chunk().borrow().span
+    }
+
+    fn new_element(&self, tag: Tag) -> Result<RNode> {
+        if tag.r#type == TagType::Close {
+            return invalid!(self.span(), "cannot insert {:#}", tag);
+        }
+        if tag.name().is_empty() {
+            return invalid!(self.span(), "cannot insert a tag without a name");
+        }
+
+        let doc: Rc<Document> = self.doc();
+        let close = (tag.r#type == TagType::Open).then(|| tag.close());
+        let node = doc.orphan(Chunk {
+            data: ChunkData::Tag(tag),
+            span: Span::inserted(),
+        });
+        if let Some(close) = close {
+            node.append(Chunk {
+                data: ChunkData::Tag(close),
+                span: Span::inserted(),
+            });
+        }
+        Ok(node)
+    }
+}
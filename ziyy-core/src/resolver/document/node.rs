@@ -4,14 +4,14 @@ use std::{
 };
 
 use super::Document;
-use crate::parser::chunk::Chunk;
-
-#[derive(Debug, Clone)]
-struct Kin {
-    parent: Option<u32>,
-    prev_sibling: Option<u32>,
-    next_sibling: Option<u32>,
-    children: Option<(u32, u32)>,
+use crate::{parser::chunk::Chunk, term::ColorLevel, width::display_width};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Kin {
+    pub(super) parent: Option<u32>,
+    pub(super) prev_sibling: Option<u32>,
+    pub(super) next_sibling: Option<u32>,
+    pub(super) children: Option<(u32, u32)>,
 }
 
 pub type RNode = Rc<Node>;
@@ -20,8 +20,8 @@ pub type RNode = Rc<Node>;
 #[doc(hidden)]
 pub struct Node {
     pub(super) id: u32,
-    kin: RefCell<Kin>,
-    doc: Weak<Document>,
+    pub(super) kin: RefCell<Kin>,
+    pub(super) doc: Weak<Document>,
     chunk: RefCell<Chunk>,
 }
 
@@ -49,7 +49,7 @@ impl Node {
     }
 
     /// Returns the chunk of this node.
-    pub fn chunk<'a>(self: &'a RNode) -> &'a RefCell<Chunk> {
+    pub fn chunk(&self) -> &RefCell<Chunk> {
         &self.chunk
     }
 
@@ -57,7 +57,8 @@ impl Node {
     where
         F: FnOnce(Ref<Kin>) -> Option<u32>,
     {
-        f(self.kin.borrow()).map(|id| self.doc().get(id))
+        let id = f(self.kin.borrow())?;
+        Some(self.doc.upgrade()?.get(id))
     }
 
     /// Returns the parent of this node.
@@ -91,12 +92,16 @@ impl Node {
     }
 
     /// Appends a new child to this node.
+    ///
+    /// See [`try_append`](Node::try_append) for a checked version.
     pub fn append(&self, value: Chunk) -> RNode {
         let id = self.doc().orphan(value).id;
         self.append_id(id)
     }
 
     /// Prepends a new child to this node.
+    ///
+    /// See [`try_prepend`](Node::try_prepend) for a checked version.
     pub fn prepend(&self, value: Chunk) -> RNode {
         let id = self.doc().orphan(value).id;
         self.prepend_id(id)
@@ -104,6 +109,9 @@ impl Node {
 
     /// Inserts a new sibling before this node.
     ///
+    /// See [`try_insert_before`](Node::try_insert_before) for a checked
+    /// version.
+    ///
     /// # Panics
     ///
     /// Panics if this node is an orphan.
@@ -114,6 +122,9 @@ impl Node {
 
     /// Inserts a new sibling after this node.
     ///
+    /// See [`try_insert_after`](Node::try_insert_after) for a checked
+    /// version.
+    ///
     /// # Panics
     ///
     /// Panics if this node is an orphan.
@@ -123,6 +134,9 @@ impl Node {
     }
 
     /// Detaches this node from its parent.
+    ///
+    /// A recycled id may be reused by a new node, which a stale `Rc<Node>`
+    /// then aliases. See [`remove`](Node::remove) for a checked version.
     pub fn detach(&self, recycle: bool) {
         let mut kin = self.kin.borrow_mut();
         let parent_id = match kin.parent {
@@ -162,6 +176,14 @@ impl Node {
         }
     }
 
+    /// Detaches this node and recycles it together with its descendants.
+    pub(crate) fn discard(&self) {
+        let doc = self.doc();
+        let subtree: Vec<_> = doc.get(self.id).descendants().map(|node| node.id).collect();
+        self.detach(false);
+        doc.recycled.borrow_mut().extend(subtree);
+    }
+
     /// Appends a child to this node.
     pub fn append_id(&self, new_child_id: u32) -> RNode {
         assert_ne!(
@@ -336,18 +358,36 @@ impl Node {
             let mut tag_chunk = self.chunk.borrow_mut();
             let tag = tag_chunk.data.tag_mut().unwrap();
             tag.reset_styles();
+            tag.set_control(String::new());
             for child in self.children() {
                 child.strip_styles();
             }
         }
     }
 
+    /// Converts the colors of this node and its descendants to the closest
+    /// colors available at `level`. [`ColorLevel::None`] strips all styles.
+    pub fn downgrade_colors(&self, level: ColorLevel) {
+        if level == ColorLevel::None {
+            return self.strip_styles();
+        }
+
+        if self.chunk.borrow().is_tag() {
+            let mut tag_chunk = self.chunk.borrow_mut();
+            let tag = tag_chunk.data.tag_mut().unwrap();
+            tag.downgrade(level);
+            for child in self.children() {
+                child.downgrade_colors(level);
+            }
+        }
+    }
+
     pub fn word_len(&self, len: &mut usize) {
         for child in self.children() {
             if child.chunk.borrow().is_word() {
-                *len += child.chunk.borrow().word().unwrap().chars().count()
+                *len += display_width(child.chunk.borrow().word().unwrap())
             } else if child.chunk.borrow().is_ws() {
-                *len += child.chunk.borrow().ws().unwrap().chars().count()
+                *len += display_width(child.chunk.borrow().ws().unwrap())
             } else {
                 child.word_len(len);
             }
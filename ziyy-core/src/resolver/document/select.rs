@@ -0,0 +1,302 @@
+use std::rc::Rc;
+
+use super::{Document, Node};
+use crate::{
+    common::{Position, Span},
+    error::{Error, ErrorType, Result},
+    parser::tag_parer::tag::{Tag, TagType},
+    splitter::is_whitespace,
+};
+
+/// A parsed list of selectors, e.g. `table td.b, p > a[href]`.
+///
+/// Supported are tag names and `*`, `.class`, `#id`, `[attr]` and
+/// `[attr=value]`, the descendant and `>` child combinators, and lists
+/// separated by `,`. Attributes match the attribute of the same name, which
+/// for `href` on `a` and `id` on `let` is the value of the tag.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, resolve};
+///
+/// let doc = resolve("<p>a <b class='x'>b</b> <i>c</i></p>", ColorLevel::TrueColor).unwrap();
+///
+/// let b = doc.select("p b.x").unwrap().unwrap();
+/// assert_eq!(b.closest("p").unwrap().unwrap().id(), b.parent().unwrap().id());
+/// assert_eq!(doc.query_all("p > *").unwrap().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    list: Vec<Vec<(Combinator, Compound)>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Compound {
+    name: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    /// Parses a selector list.
+    pub fn parse(source: &str) -> Result<Self> {
+        SelectorParser { source, current: 0 }.parse()
+    }
+
+    /// Returns true if `node` matches this selector.
+    pub fn matches(&self, node: &Rc<Node>) -> bool {
+        self.list.iter().any(|complex| matches(complex, node))
+    }
+}
+
+fn matches(complex: &[(Combinator, Compound)], node: &Rc<Node>) -> bool {
+    let Some(((combinator, compound), rest)) = complex.split_last() else {
+        return true;
+    };
+    if !compound.matches(node) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+
+    match combinator {
+        Combinator::Child => node.parent().is_some_and(|parent| matches(rest, &parent)),
+        Combinator::Descendant => node.ancestors().skip(1).any(|node| matches(rest, &node)),
+    }
+}
+
+impl Compound {
+    fn matches(&self, node: &Node) -> bool {
+        let chunk = node.chunk().borrow();
+        let Some(tag) = chunk.tag() else {
+            return false;
+        };
+        if tag.r#type == TagType::Close {
+            return false;
+        }
+
+        self.name.as_ref().is_none_or(|name| name == tag.name())
+            && self.classes.iter().all(|class| has_class(tag, class))
+            && self.attributes.iter().all(|(name, value)| {
+                attribute(tag, name).is_some_and(|attr| value.as_ref().is_none_or(|v| v == attr))
+            })
+    }
+}
+
+/// Returns the attribute `name` of `tag`, including those the parser keeps
+/// as the value of the tag.
+fn attribute<'a>(tag: &'a Tag, name: &str) -> Option<&'a str> {
+    match (tag.name().as_str(), name) {
+        (_, "class") => Some(tag.class().as_str()).filter(|s| !s.is_empty()),
+        ("a", "href") | ("let", "id") => Some(tag.custom().as_str()).filter(|s| !s.is_empty()),
+        _ => tag.attribute(name),
+    }
+}
+
+fn has_class(tag: &Tag, class: &str) -> bool {
+    tag.class().split(is_whitespace).any(|s| s == class)
+}
+
+struct SelectorParser<'a> {
+    source: &'a str,
+    current: usize,
+}
+
+impl SelectorParser<'_> {
+    fn parse(&mut self) -> Result<Selector> {
+        let mut list = vec![];
+        loop {
+            list.push(self.complex()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.current += 1,
+                None => break,
+                Some(c) => return Err(self.error(format!("unexpected {c:?}"))),
+            }
+        }
+        Ok(Selector { list })
+    }
+
+    fn complex(&mut self) -> Result<Vec<(Combinator, Compound)>> {
+        self.skip_whitespace();
+        let mut complex = vec![(Combinator::Descendant, self.compound()?)];
+        loop {
+            let skipped = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => {
+                    self.current += 1;
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(',') | None => break,
+                Some(_) if skipped => Combinator::Descendant,
+                Some(c) => return Err(self.error(format!("unexpected {c:?}"))),
+            };
+            complex.push((combinator, self.compound()?));
+        }
+        Ok(complex)
+    }
+
+    fn compound(&mut self) -> Result<Compound> {
+        let start = self.current;
+        let mut compound = Compound::default();
+
+        if self.peek() == Some('*') {
+            self.current += 1;
+        } else if self.peek().is_some_and(is_ident) {
+            compound.name = Some(self.ident()?);
+        }
+
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.current += 1;
+                    compound.classes.push(self.ident()?);
+                }
+                Some('#') => {
+                    self.current += 1;
+                    let id = self.ident()?;
+                    compound.attributes.push(("id".to_string(), Some(id)));
+                }
+                Some('[') => {
+                    self.current += 1;
+                    self.skip_whitespace();
+                    let name = self.ident()?;
+                    self.skip_whitespace();
+                    let value = if self.peek() == Some('=') {
+                        self.current += 1;
+                        self.skip_whitespace();
+                        Some(self.value()?)
+                    } else {
+                        None
+                    };
+                    self.skip_whitespace();
+                    self.expect(']')?;
+                    compound.attributes.push((name, value));
+                }
+                _ => break,
+            }
+        }
+
+        if self.current == start {
+            return Err(match self.peek() {
+                Some(c) => self.error(format!("unexpected {c:?}")),
+                None => self.error("expected a selector".to_string()),
+            });
+        }
+        Ok(compound)
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        let start = self.current;
+        while self.peek().is_some_and(is_ident) {
+            self.current += 1;
+        }
+        if start == self.current {
+            return Err(self.error("expected a name".to_string()));
+        }
+        Ok(self.source[start..self.current].to_string())
+    }
+
+    fn value(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.current += 1;
+                let start = self.current;
+                let Some(len) = self.source[start..].find(quote) else {
+                    return Err(Error::new(
+                        ErrorType::UnterminatedString,
+                        "Untermitated string literal".to_string(),
+                        self.span(),
+                    ));
+                };
+                self.current += len + 1;
+                Ok(self.source[start..start + len].to_string())
+            }
+            _ => self.ident(),
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.peek() == Some(c) {
+            self.current += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {c:?}")))
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.current;
+        while self.peek().is_some_and(is_whitespace) {
+            self.current += 1;
+        }
+        start != self.current
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.current..].chars().next()
+    }
+
+    fn span(&self) -> Span {
+        let pos = Position::locate(self.source, self.current);
+        Span::new(pos, pos)
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::new(ErrorType::InvalidSelector, message, self.span())
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '$')
+}
+
+impl Document {
+    /// Returns the first node matching `selector`.
+    pub fn select(&self, selector: &str) -> Result<Option<Rc<Node>>> {
+        self.root().select(selector)
+    }
+
+    /// Returns all nodes matching `selector`, in document order.
+    pub fn query_all(&self, selector: &str) -> Result<Vec<Rc<Node>>> {
+        self.root().query_all(selector)
+    }
+}
+
+impl Node {
+    /// Returns the first descendant of this node matching `selector`.
+    pub fn select(self: &Rc<Node>, selector: &str) -> Result<Option<Rc<Node>>> {
+        let selector = Selector::parse(selector)?;
+        Ok(self
+            .descendants()
+            .skip(1)
+            .find(|node| selector.matches(node)))
+    }
+
+    /// Returns all descendants of this node matching `selector`, in document order.
+    pub fn query_all(self: &Rc<Node>, selector: &str) -> Result<Vec<Rc<Node>>> {
+        let selector = Selector::parse(selector)?;
+        Ok(self
+            .descendants()
+            .skip(1)
+            .filter(|node| selector.matches(node))
+            .collect())
+    }
+
+    /// Returns the closest node matching `selector`, starting at this node
+    /// and going up through its ancestors.
+    pub fn closest(self: &Rc<Node>, selector: &str) -> Result<Option<Rc<Node>>> {
+        let selector = Selector::parse(selector)?;
+        Ok(self.ancestors().find(|node| selector.matches(node)))
+    }
+}
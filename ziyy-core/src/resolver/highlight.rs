@@ -0,0 +1,504 @@
+use std::{ops::Range, rc::Rc};
+
+use super::{Resolver, diff::unified, document::Node};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::{Tag, TagType},
+    },
+    splitter::is_whitespace,
+};
+
+/// Default styles of the token classes, which `<let id="code.keyword">`
+/// and the like override.
+pub(super) const THEME: [(&str, &str); 22] = [
+    ("code.keyword", "<let c='magenta'>"),
+    ("code.type", "<let c='yellow'>"),
+    ("code.string", "<let c='green'>"),
+    ("code.number", "<let c='cyan'>"),
+    ("code.literal", "<let c='cyan'>"),
+    ("code.comment", "<let d i>"),
+    ("code.key", "<let c='blue'>"),
+    ("code.section", "<let c='blue' b>"),
+    ("code.variable", "<let c='cyan'>"),
+    ("code.macro", "<let c='blue'>"),
+    ("code.meta", "<let b>"),
+    ("code.hunk", "<let c='cyan'>"),
+    ("code.added", "<let c='green'>"),
+    ("code.removed", "<let c='red'>"),
+    ("code.context", "<let>"),
+    ("diff.meta", "<let b>"),
+    ("diff.hunk", "<let c='cyan'>"),
+    ("diff.added", "<let c='green'>"),
+    ("diff.removed", "<let c='red'>"),
+    ("diff.context", "<let>"),
+    ("diff.added.word", "<let r>"),
+    ("diff.removed.word", "<let r>"),
+];
+
+/// A token of highlighted text and its class, such as `keyword`.
+pub(super) type Token = (Range<usize>, &'static str);
+
+impl Resolver {
+    /// Highlights the text of all `code` elements with a `lang` of `rust`,
+    /// `json`, `toml`, `shell` or `diff`, and of all `diff` elements.
+    ///
+    /// Every token becomes an element named after its class, like
+    /// `code.keyword` or `diff.added`, which are styled by [`THEME`] unless
+    /// they are bound with `<let>`. The changed words of `diff` elements with
+    /// a `words` attribute are highlighted as `diff.added.word` and
+    /// `diff.removed.word`.
+    pub(super) fn highlight(&self) {
+        for node in &self.codes {
+            let mut text = String::new();
+            for child in node.descendants().skip(1) {
+                match &child.chunk().borrow().data {
+                    ChunkData::Word(s) | ChunkData::WhiteSpace(s) => text.push_str(s),
+                    ChunkData::Tag(tag) if tag.name() == "br" => {
+                        let n = tag.custom().parse().unwrap_or(1);
+                        text.extend((0..n).map(|_| '\n'));
+                    }
+                    ChunkData::Tag(_) => {}
+                }
+            }
+
+            let (prefix, tokens) = {
+                let chunk = node.chunk().borrow();
+                let tag = chunk.tag().unwrap();
+                if tag.name() == "diff" {
+                    ("diff", unified(&text, tag.attribute("words").is_some()))
+                } else {
+                    let lang = tag.attribute("lang").map(str::to_lowercase);
+                    match lang.as_deref().and_then(tokenizer) {
+                        Some(tokenize) => ("code", tokenize(&text)),
+                        None => continue,
+                    }
+                }
+            };
+
+            let close = node.last_child().filter(|last| {
+                last.chunk()
+                    .borrow()
+                    .is_tag_and(|tag| tag.r#type == TagType::Close)
+            });
+            for child in node.children().collect::<Vec<_>>() {
+                if close.as_ref().is_none_or(|close| close.id() != child.id()) {
+                    child.discard();
+                }
+            }
+            let append = |chunk: Chunk| match &close {
+                Some(close) => close.insert_before(chunk),
+                None => node.append(chunk),
+            };
+
+            build(&text, 0..text.len(), &tokens, prefix, &append);
+        }
+    }
+}
+
+/// Appends the text in `range` with an element for every token. Tokens
+/// follow the tokens they are nested in.
+fn build(
+    text: &str,
+    range: Range<usize>,
+    tokens: &[Token],
+    prefix: &str,
+    append: &dyn Fn(Chunk) -> Rc<Node>,
+) {
+    let mut end = range.start;
+    let mut i = 0;
+    while let Some((token, class)) = tokens.get(i) {
+        let nested = tokens[i + 1..]
+            .iter()
+            .take_while(|(range, _)| range.start < token.end)
+            .count();
+        let children = &tokens[i + 1..i + 1 + nested];
+        i += 1 + nested;
+
+        split(&text[end..token.start], append);
+        // tokens never span lines, so that nothing else is styled like them
+        let mut start = token.start;
+        for line in text[token.clone()].split_inclusive('\n') {
+            let line = start..start + line.trim_end_matches('\n').len();
+            if !line.is_empty() {
+                let mut tag = Tag::with_name(&format!("{prefix}.{class}"));
+                tag.r#type = TagType::Open;
+                let element = append(Chunk {
+                    data: ChunkData::Tag(tag.clone()),
+                    span: Span::inserted(),
+                });
+                let children: Vec<Token> = children
+                    .iter()
+                    .filter(|(range, _)| line.start <= range.start && range.end <= line.end)
+                    .cloned()
+                    .collect();
+                build(text, line.clone(), &children, prefix, &|chunk| {
+                    element.append(chunk)
+                });
+                element.append(Chunk {
+                    data: ChunkData::Tag(tag.close()),
+                    span: Span::inserted(),
+                });
+            }
+            split(&text[line.end..token.end.min(line.end + 1)], append);
+            start = line.end + 1;
+        }
+        end = token.end;
+    }
+    split(&text[end..range.end], append);
+}
+
+/// Appends `text` as words and whitespace.
+fn split(text: &str, append: &dyn Fn(Chunk) -> Rc<Node>) {
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let ws = is_whitespace(c);
+        if chars
+            .peek()
+            .is_none_or(|&(_, next)| is_whitespace(next) != ws)
+        {
+            let s = text[start..i + c.len_utf8()].to_string();
+            append(Chunk {
+                data: if ws {
+                    ChunkData::WhiteSpace(s)
+                } else {
+                    ChunkData::Word(s)
+                },
+                span: Span::inserted(),
+            });
+            start = i + c.len_utf8();
+        }
+    }
+}
+
+fn tokenizer(lang: &str) -> Option<fn(&str) -> Vec<Token>> {
+    match lang {
+        "rust" | "rs" => Some(rust),
+        "json" => Some(json),
+        "toml" => Some(toml),
+        "shell" | "sh" | "bash" | "zsh" | "console" => Some(shell),
+        "diff" | "patch" => Some(|text| unified(text, false)),
+        _ => None,
+    }
+}
+
+/// A cursor over text being tokenized.
+struct Lexer<'a> {
+    text: &'a str,
+    pos: usize,
+    tokens: Vec<Token>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: 0,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    fn peek(&self) -> char {
+        self.text[self.pos..].chars().next().unwrap_or('\0')
+    }
+
+    fn peek_next(&self) -> char {
+        let mut chars = self.text[self.pos..].chars();
+        chars.next();
+        chars.next().unwrap_or('\0')
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Consumes the next character, which is `'\0'` at the end.
+    fn advance(&mut self) -> char {
+        if self.is_at_end() {
+            return '\0';
+        }
+        let c = self.peek();
+        self.pos += c.len_utf8();
+        c
+    }
+
+    fn eat_while(&mut self, f: impl Fn(char) -> bool) {
+        while !self.is_at_end() && f(self.peek()) {
+            self.advance();
+        }
+    }
+
+    /// Consumes a string closed by `quote`, skipping escaped characters when
+    /// `escapes` is set.
+    fn string(&mut self, quote: &str, escapes: bool) {
+        while !self.is_at_end() && !self.rest().starts_with(quote) {
+            if self.advance() == '\\' && escapes {
+                self.advance();
+            }
+        }
+        self.pos = (self.pos + quote.len()).min(self.text.len());
+    }
+
+    /// Consumes the rest of the line without its line break.
+    fn line(&mut self) {
+        self.eat_while(|c| c != '\n');
+    }
+
+    /// Returns whether the current position starts a line, ignoring
+    /// indentation.
+    fn at_line_start(&self) -> bool {
+        self.text[..self.pos]
+            .rsplit('\n')
+            .next()
+            .is_none_or(|line| line.chars().all(|c| c == ' ' || c == '\t'))
+    }
+
+    fn push(&mut self, start: usize, class: &'static str) {
+        if start < self.pos {
+            self.tokens.push((start..self.pos, class));
+        }
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn number(lexer: &mut Lexer) {
+    lexer.eat_while(is_ident);
+    while lexer.peek() == '.' && lexer.peek_next().is_ascii_digit() {
+        lexer.advance();
+        lexer.eat_while(is_ident);
+    }
+}
+
+fn rust(text: &str) -> Vec<Token> {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ];
+    const PRIMITIVES: &[&str] = &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8",
+        "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    let mut lexer = Lexer::new(text);
+    while !lexer.is_at_end() {
+        let start = lexer.pos;
+        let rest = lexer.rest();
+        if rest.starts_with("//") {
+            lexer.line();
+            lexer.push(start, "comment");
+        } else if rest.starts_with("/*") {
+            lexer.advance();
+            lexer.advance();
+            lexer.string("*/", false);
+            lexer.push(start, "comment");
+        } else if rest.starts_with("#[") || rest.starts_with("#![") {
+            lexer.eat_while(|c| c != ']' && c != '\n');
+            if lexer.peek() == ']' {
+                lexer.advance();
+            }
+            lexer.push(start, "meta");
+        } else if let Some(hashes) = raw_string(rest) {
+            lexer.pos += rest.find('"').unwrap() + 1;
+            lexer.string(&format!("\"{}", "#".repeat(hashes)), false);
+            lexer.push(start, "string");
+        } else if rest.starts_with('"') || rest.starts_with("b\"") {
+            lexer.eat_while(|c| c != '"');
+            lexer.advance();
+            lexer.string("\"", true);
+            lexer.push(start, "string");
+        } else if rest.starts_with('\'') {
+            lexer.advance();
+            let c = lexer.advance();
+            if c == '\\' || lexer.peek() == '\'' {
+                if c == '\\' {
+                    lexer.advance();
+                }
+                lexer.string("'", true);
+                lexer.push(start, "string");
+            } else {
+                // a lifetime
+                lexer.eat_while(is_ident);
+                lexer.push(start, "type");
+            }
+        } else {
+            let c = lexer.peek();
+            if c.is_ascii_digit() {
+                number(&mut lexer);
+                lexer.push(start, "number");
+            } else if is_ident(c) {
+                lexer.eat_while(is_ident);
+                let word = &text[start..lexer.pos];
+                if lexer.peek() == '!' && lexer.peek_next() != '=' {
+                    lexer.advance();
+                    lexer.push(start, "macro");
+                } else if KEYWORDS.contains(&word) {
+                    lexer.push(start, "keyword");
+                } else if matches!(word, "true" | "false") {
+                    lexer.push(start, "literal");
+                } else if PRIMITIVES.contains(&word) || word.starts_with(char::is_uppercase) {
+                    lexer.push(start, "type");
+                }
+            } else {
+                lexer.advance();
+            }
+        }
+    }
+    lexer.tokens
+}
+
+/// Returns the number of hashes of a raw string starting `text`.
+fn raw_string(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix("br").or(text.strip_prefix('r'))?;
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    rest[hashes..].starts_with('"').then_some(hashes)
+}
+
+fn json(text: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(text);
+    while !lexer.is_at_end() {
+        let start = lexer.pos;
+        let c = lexer.advance();
+        match c {
+            '"' => {
+                lexer.string("\"", true);
+                let after = lexer.rest().trim_start_matches(is_whitespace);
+                let class = if after.starts_with(':') {
+                    "key"
+                } else {
+                    "string"
+                };
+                lexer.push(start, class);
+            }
+            '-' | '0'..='9' => {
+                lexer.eat_while(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'));
+                lexer.push(start, "number");
+            }
+            c if c.is_alphabetic() => {
+                lexer.eat_while(char::is_alphanumeric);
+                if matches!(&text[start..lexer.pos], "true" | "false" | "null") {
+                    lexer.push(start, "literal");
+                }
+            }
+            _ => {}
+        }
+    }
+    lexer.tokens
+}
+
+fn toml(text: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(text);
+    // whether a key is expected before the next `=`
+    let mut key = true;
+    while !lexer.is_at_end() {
+        let start = lexer.pos;
+        let rest = lexer.rest();
+        let c = lexer.peek();
+        if c == '\n' {
+            lexer.advance();
+            key = true;
+        } else if c == '#' {
+            lexer.line();
+            lexer.push(start, "comment");
+        } else if c == '[' && key && lexer.at_line_start() {
+            lexer.eat_while(|c| c != ']' && c != '\n');
+            lexer.eat_while(|c| c == ']');
+            lexer.push(start, "section");
+        } else if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+            let quote = &rest[..3];
+            lexer.pos += 3;
+            lexer.string(quote, quote == "\"\"\"");
+            lexer.push(start, "string");
+        } else if c == '"' || c == '\'' {
+            lexer.advance();
+            lexer.string(&c.to_string(), c == '"');
+            lexer.push(start, if key { "key" } else { "string" });
+        } else if c == '=' {
+            lexer.advance();
+            key = false;
+        } else if key && (is_ident(c) || c == '-') {
+            lexer.eat_while(|c| is_ident(c) || c == '-');
+            lexer.push(start, "key");
+        } else if c.is_ascii_digit()
+            || ((c == '-' || c == '+') && lexer.peek_next().is_ascii_digit())
+        {
+            lexer.advance();
+            lexer.eat_while(|c| is_ident(c) || matches!(c, '.' | ':' | '-' | '+'));
+            lexer.push(start, "number");
+        } else if is_ident(c) {
+            lexer.eat_while(is_ident);
+            if matches!(&text[start..lexer.pos], "true" | "false" | "inf" | "nan") {
+                lexer.push(start, "literal");
+            }
+        } else {
+            lexer.advance();
+        }
+    }
+    lexer.tokens
+}
+
+fn shell(text: &str) -> Vec<Token> {
+    const KEYWORDS: &[&str] = &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ];
+
+    let mut lexer = Lexer::new(text);
+    // whether the current position starts a word
+    let mut word = true;
+    while !lexer.is_at_end() {
+        let start = lexer.pos;
+        let c = lexer.peek();
+        if c == '#' && word {
+            lexer.line();
+            lexer.push(start, "comment");
+        } else if c == '\'' {
+            lexer.advance();
+            lexer.string("'", false);
+            lexer.push(start, "string");
+        } else if c == '"' {
+            lexer.advance();
+            lexer.string("\"", true);
+            lexer.push(start, "string");
+        } else if c == '$' && lexer.peek_next() == '{' {
+            lexer.eat_while(|c| c != '}' && c != '\n');
+            if lexer.peek() == '}' {
+                lexer.advance();
+            }
+            lexer.push(start, "variable");
+        } else if c == '$' && (is_ident(lexer.peek_next()) || "?#@*!$".contains(lexer.peek_next()))
+        {
+            lexer.advance();
+            if is_ident(lexer.peek()) {
+                lexer.eat_while(is_ident);
+            } else {
+                lexer.advance();
+            }
+            lexer.push(start, "variable");
+        } else if is_ident(c) && word {
+            lexer.eat_while(|c| is_ident(c) || c == '-');
+            let end = lexer.pos;
+            let is_word_end =
+                lexer.is_at_end() || is_whitespace(lexer.peek()) || lexer.peek() == ';';
+            if KEYWORDS.contains(&&text[start..end]) && is_word_end {
+                lexer.push(start, "keyword");
+            }
+            word = false;
+            continue;
+        } else {
+            lexer.advance();
+        }
+        word = is_whitespace(c) || matches!(c, ';' | '|' | '&' | '(');
+    }
+    lexer.tokens
+}
@@ -0,0 +1,172 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{close_element, word, ws},
+};
+use crate::{
+    common::Span,
+    image::{self, Image},
+    parser::{
+        chunk::{Chunk, ChunkData},
+        color::{Color, Rgb},
+        tag_parer::tag::{Tag, TagType},
+    },
+    term::Graphics,
+};
+
+/// Width of images in cells when neither the image nor the resolver limit it.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Most rows an image may take.
+const MAX_ROWS: usize = 200;
+
+/// Size in pixels of a cell, which sixel images are scaled by.
+const CELL: (usize, usize) = (10, 20);
+
+impl Resolver {
+    /// Shows the PNG or PPM image `src` as `width` cells wide and `height`
+    /// rows high, keeping its aspect ratio when only one of them is given.
+    /// Images are never wider than the resolver width.
+    ///
+    /// Without a size, every pixel takes a cell. The image is written with
+    /// the graphics of the resolver, and as its `alt` text when it cannot be
+    /// read, decoded or shown. Only files which decode as an image are ever
+    /// written out, so `src` cannot pull other files into the output.
+    pub(super) fn set_image(&self, node: &Rc<Node>) {
+        let graphics = self.graphics;
+        let (src, columns, rows, alt) = {
+            let chunk = node.chunk().borrow();
+            let tag = chunk.tag().unwrap();
+            (
+                tag.attribute("src").unwrap_or_default().to_string(),
+                tag.usize_attribute("width").filter(|&n| n > 0),
+                tag.usize_attribute("height").filter(|&n| n > 0),
+                tag.attribute("alt").unwrap_or_default().to_string(),
+            )
+        };
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+
+        let data = match graphics {
+            Graphics::Alt => None,
+            _ => image::read(&src),
+        };
+        // images never grow past the resolver width, whatever their size
+        let max = self.width.unwrap_or(DEFAULT_WIDTH).max(1);
+        let columns = columns.map(|columns| columns.min(max));
+        let rows = rows.map(|rows| rows.min(MAX_ROWS));
+        let image = data.as_deref().and_then(Image::decode);
+        let size = image.as_ref().map(|image| size(image, columns, rows, max));
+
+        let control = match (graphics, &data, &image, size) {
+            (Graphics::Kitty, Some(data), Some(image), Some((columns, rows))) => {
+                Some(image::kitty(data, image, columns, rows))
+            }
+            (Graphics::Iterm2, Some(data), Some(_), Some((columns, rows))) => {
+                Some(image::iterm2(data, columns, Some(rows)))
+            }
+            (Graphics::Sixel, _, Some(image), Some((columns, rows))) => {
+                Some(image::sixel(&image.resize(columns * CELL.0, rows * CELL.1)))
+            }
+            _ => None,
+        };
+
+        let mut chunk = node.chunk().borrow_mut();
+        let tag = chunk.tag_mut().unwrap();
+        if let Some(control) = control {
+            tag.set_control(control);
+            tag.r#type = TagType::SelfClose;
+            return;
+        }
+        tag.r#type = TagType::Open;
+        drop(chunk);
+
+        match (graphics, image, size) {
+            (Graphics::Blocks, Some(image), Some((columns, rows))) => {
+                blocks(node, &image.resize(columns, rows * 2));
+            }
+            _ if !alt.is_empty() => {
+                node.append(word(alt));
+            }
+            _ => {}
+        }
+        close_element(node);
+    }
+}
+
+/// Returns the size in cells of `image`, given its `columns` and `rows` if
+/// any, and at most `max` columns. A cell is taken to be twice as high as it
+/// is wide.
+fn size(image: &Image, columns: Option<usize>, rows: Option<usize>, max: usize) -> (usize, usize) {
+    let (width, height) = (image.width as f64, image.height as f64);
+    let (columns, rows) = match (columns, rows) {
+        (Some(columns), Some(rows)) => (columns, rows),
+        (Some(columns), None) => (
+            columns,
+            (height * columns as f64 / width / 2.0).round() as usize,
+        ),
+        (None, Some(rows)) => ((width * rows as f64 * 2.0 / height).round() as usize, rows),
+        (None, None) => {
+            let columns = image.width.min(max);
+            (
+                columns,
+                (height * columns as f64 / width / 2.0).round() as usize,
+            )
+        }
+    };
+    (columns.clamp(1, max), rows.clamp(1, MAX_ROWS))
+}
+
+/// Draws `image` with a half block for every two pixels above each other,
+/// colored with the upper pixel and backed by the lower one. Cells of the
+/// same colors are drawn together.
+fn blocks(node: &Rc<Node>, image: &Image) {
+    let color = |[r, g, b, a]: [u8; 4], n| (a >= 128).then_some(Rgb(r, g, b, n));
+    for y in (0..image.height).step_by(2) {
+        if y > 0 {
+            node.append(ws("\n".to_string()));
+        }
+        let mut cells: Vec<(Option<Rgb>, Option<Rgb>, &str)> = Vec::with_capacity(image.width);
+        for x in 0..image.width {
+            let top = image.pixels[y * image.width + x];
+            let bottom = match y + 1 < image.height {
+                true => image.pixels[(y + 1) * image.width + x],
+                false => [0; 4],
+            };
+            cells.push(match (color(top, 38), color(bottom, 48)) {
+                // a lower half block shows the lower pixel alone
+                (None, Some(Rgb(r, g, b, _))) => (Some(Rgb(r, g, b, 38)), None, "▄"),
+                (None, None) => (None, None, " "),
+                (top, bottom) => (top, bottom, "▀"),
+            });
+        }
+
+        let mut start = 0;
+        while start < cells.len() {
+            let (fg, bg, _) = &cells[start];
+            let end = cells[start..]
+                .iter()
+                .position(|(f, b, _)| f != fg || b != bg)
+                .map_or(cells.len(), |n| start + n);
+            let text: String = cells[start..end].iter().map(|(_, _, s)| *s).collect();
+            let mut tag = Tag::with_name("c");
+            tag.r#type = TagType::Open;
+            if let Some(fg) = fg {
+                tag.set_fg_color(Color::Rgb(fg.clone()));
+            }
+            if let Some(bg) = bg {
+                tag.set_bg_color(Color::Rgb(bg.clone()));
+            }
+            let element = node.append(Chunk {
+                data: ChunkData::Tag(tag),
+                span: Span::inserted(),
+            });
+            element.append(word(text));
+            close_element(&element);
+            start = end;
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::rc::Rc;
+
+use super::{Resolver, align::is_aligned, document::Node};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::TagType,
+    },
+    width::display_width,
+};
+
+/// The block a node is laid out in.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    /// Whether lines are wrapped at `width`.
+    wrap: bool,
+    /// Column lines may not exceed.
+    width: Option<usize>,
+    /// Column wrapped lines start at.
+    indent: usize,
+    /// Whether lines after `<br>` start at `indent` even when not wrapping.
+    breaks: bool,
+}
+
+/// Position of the text being laid out.
+#[derive(Debug, Default)]
+struct Cursor {
+    column: usize,
+    /// The last whitespace of the current line and the column after it.
+    space: Option<(Rc<Node>, usize)>,
+    /// Line breaks which need to be followed by indentation.
+    breaks: Vec<(Rc<Node>, usize)>,
+}
+
+impl Resolver {
+    /// Wraps the text of `p`, `div` and `li` elements to their width.
+    ///
+    /// The width of a block is the resolver width or the `width` attribute,
+    /// whichever is smaller. Wrapped lines start at the `indent` of the block
+    /// plus its `hang`, and `pre` elements are left untouched.
+    pub(super) fn wrap(&self, node: &Rc<Node>) {
+        let block = Block {
+            wrap: false,
+            width: self.width,
+            indent: 0,
+            breaks: false,
+        };
+        let mut cursor = Cursor::default();
+        Resolver::wrap_children(node, block, &mut cursor);
+
+        for (br, indent) in cursor.breaks {
+            br.insert_after(Chunk {
+                data: ChunkData::WhiteSpace(" ".repeat(indent)),
+                span: Span::inserted(),
+            });
+        }
+    }
+
+    fn wrap_children(node: &Rc<Node>, block: Block, cursor: &mut Cursor) {
+        for child in node.children() {
+            let chunk = child.chunk().borrow();
+            match &chunk.data {
+                ChunkData::Word(word) => {
+                    let len = display_width(word);
+                    if block.wrap
+                        && block.width.is_some_and(|width| cursor.column + len > width)
+                        && let Some((space, column)) = cursor.space.take()
+                    {
+                        space.chunk().borrow_mut().data =
+                            ChunkData::WhiteSpace(format!("\n{}", " ".repeat(block.indent)));
+                        cursor.column = block.indent + cursor.column - column;
+                    }
+                    cursor.column += len;
+                }
+
+                ChunkData::WhiteSpace(ws) => match ws.rfind('\n') {
+                    Some(i) => {
+                        cursor.column = display_width(&ws[i + 1..]);
+                        cursor.space = None;
+                    }
+                    None => {
+                        let start = cursor.column;
+                        let mut len = display_width(ws);
+                        // whitespace past the width, such as table padding, would
+                        // make the terminal wrap on its own
+                        if let Some(width) = block.width.filter(|_| block.wrap)
+                            && start + len > width
+                        {
+                            len = width.saturating_sub(start);
+                            drop(chunk);
+                            child.chunk().borrow_mut().data =
+                                ChunkData::WhiteSpace(" ".repeat(len));
+                        }
+                        cursor.column += len;
+                        if block.wrap && start > block.indent {
+                            cursor.space = Some((child.clone(), cursor.column));
+                        }
+                    }
+                },
+
+                ChunkData::Tag(tag) => {
+                    let name = tag.name().as_str();
+                    if name == "br" {
+                        cursor.column = 0;
+                        cursor.space = None;
+                        if block.wrap && (block.width.is_some() || block.breaks) && block.indent > 0
+                        {
+                            cursor.breaks.push((child.clone(), block.indent));
+                            cursor.column = block.indent;
+                        }
+                        continue;
+                    }
+                    if tag.r#type != TagType::Open {
+                        continue;
+                    }
+
+                    let hang = tag.usize_attribute("hang").unwrap_or(0);
+                    let inner = match name {
+                        "p" | "div" => {
+                            let indent = tag.custom().parse().unwrap_or(0);
+                            cursor.column += indent;
+                            Block {
+                                // aligned blocks are laid out by `set_aligned`
+                                wrap: !is_aligned(tag),
+                                width: min(block.width, tag.usize_attribute("width")),
+                                indent: indent + hang,
+                                breaks: false,
+                            }
+                        }
+                        // the hang of list items is set by `set_lists`
+                        "li" => Block {
+                            wrap: true,
+                            width: block.width,
+                            indent: hang,
+                            breaks: true,
+                        },
+                        // tables, boxes and images are laid out by `set_blocks`
+                        "pre" | "code" | "diff" | "table" | "box" | "img" => Block {
+                            wrap: false,
+                            ..block
+                        },
+                        _ => block,
+                    };
+
+                    let is_block = matches!(
+                        name,
+                        "p" | "div" | "pre" | "code" | "diff" | "table" | "li" | "box"
+                    );
+                    if is_block {
+                        cursor.space = None;
+                    }
+                    Resolver::wrap_children(&child, inner, cursor);
+                    if is_block {
+                        cursor.space = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
@@ -0,0 +1,78 @@
+use super::{
+    Resolver,
+    table::{word, ws},
+};
+use crate::{
+    hyperlink::{escape_href, escape_id, file_url, is_valid_href},
+    parser::{chunk::ChunkData, tag_parer::tag::TagType},
+};
+
+impl Resolver {
+    /// Prepares the targets of all links.
+    ///
+    /// A `file` attribute links to the `file://` URL of its path, the target
+    /// is escaped and so is an `id`, which lets terminals treat the parts of
+    /// a link broken over several lines as one. Without hyperlinks, or when
+    /// the target is not a URL terminals open, the target is written after
+    /// the text of the link instead, unless it is the same.
+    pub(super) fn set_links(&self) {
+        for node in &self.links {
+            let href = {
+                let mut chunk = node.chunk().borrow_mut();
+                let tag = chunk.tag_mut().unwrap();
+                if let Some(file) = tag.attribute("file") {
+                    let href = file_url(file).unwrap_or_else(|_| file.to_string());
+                    tag.set_custom(href);
+                }
+                if let Some(id) = tag.attribute("id") {
+                    let id = escape_id(id);
+                    tag.set_attribute("id", id);
+                }
+                let href = escape_href(tag.custom()).into_owned();
+                tag.set_custom(href.clone());
+                href
+            };
+            if self.hyperlinks && is_valid_href(&href) {
+                continue;
+            }
+
+            let mut text = String::new();
+            for child in node.descendants().skip(1) {
+                if let ChunkData::Word(s) | ChunkData::WhiteSpace(s) = &child.chunk().borrow().data
+                {
+                    text.push_str(s);
+                }
+            }
+
+            // a link without its target is just text
+            node.chunk()
+                .borrow_mut()
+                .tag_mut()
+                .unwrap()
+                .set_name("span".to_string());
+            let close = node.last_child().filter(|last| {
+                last.chunk()
+                    .borrow()
+                    .is_tag_and(|tag| tag.r#type == TagType::Close)
+            });
+            if let Some(close) = &close {
+                close
+                    .chunk()
+                    .borrow_mut()
+                    .tag_mut()
+                    .unwrap()
+                    .set_name("span".to_string());
+            }
+            if href.is_empty() || text.trim() == href {
+                continue;
+            }
+            let target = [ws(" ".to_string()), word(format!("({href})"))];
+            for chunk in target {
+                match &close {
+                    Some(close) => close.insert_before(chunk),
+                    None => node.append(chunk),
+                };
+            }
+        }
+    }
+}
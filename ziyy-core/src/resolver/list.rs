@@ -0,0 +1,165 @@
+use std::rc::Rc;
+
+use super::{Resolver, document::Node};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::TagType,
+    },
+    width::display_width,
+};
+
+/// Bullets of unordered lists, by nesting depth.
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+impl Resolver {
+    /// Inserts the markers of all `ul` and `ol` lists found while parsing
+    /// words.
+    ///
+    /// A `ul` takes any `marker`, and an `ol` takes a `marker` in which the
+    /// last `1`, `a`, `A`, `i` or `I` is replaced by the decimal, alphabetic
+    /// or roman number of the item, counting from `start`. Markers are right
+    /// aligned, and nested lists and wrapped lines start under the text of
+    /// their item.
+    pub(super) fn set_lists(&self) {
+        // enclosing lists come first, so the items nested lists are part of
+        // already know where their text starts
+        for list in &self.lists {
+            set_list(list);
+        }
+    }
+}
+
+fn set_list(list: &Rc<Node>) {
+    let (ordered, marker, start, indent) = {
+        let chunk = list.chunk().borrow();
+        let tag = chunk.tag().unwrap();
+        (
+            tag.name() == "ol",
+            tag.attribute("marker").map(str::to_string),
+            tag.usize_attribute("start").unwrap_or(1),
+            tag.custom().parse().unwrap_or(0),
+        )
+    };
+
+    let mut base = indent;
+    let mut depth = 0;
+    for ancestor in list.ancestors().skip(1) {
+        let chunk = ancestor.chunk().borrow();
+        let Some(tag) = chunk.tag() else {
+            continue;
+        };
+        match tag.name().as_str() {
+            "li" if depth == 0 => base += tag.usize_attribute("hang").unwrap_or(0),
+            "ul" | "ol" => depth += 1,
+            _ => {}
+        }
+    }
+
+    let mut items = Vec::with_capacity(16);
+    for child in list.children().collect::<Vec<_>>() {
+        let chunk = child.chunk().borrow();
+        match &chunk.data {
+            ChunkData::WhiteSpace(_) => {
+                drop(chunk);
+                child.discard();
+            }
+            ChunkData::Tag(tag) if tag.r#type == TagType::Open && tag.name() == "li" => {
+                items.push(child.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let markers: Vec<String> = (0..items.len())
+        .map(|i| match (&marker, ordered) {
+            (Some(marker), true) => counter(marker, start + i),
+            (None, true) => counter("1.", start + i),
+            (Some(marker), false) => marker.clone(),
+            (None, false) => BULLETS[depth % BULLETS.len()].to_string(),
+        })
+        .collect();
+    let width = markers.iter().map(|s| display_width(s)).max().unwrap_or(0);
+
+    for (item, marker) in items.iter().zip(markers) {
+        let pad = base + width - display_width(&marker);
+        item.prepend(Chunk {
+            data: ChunkData::WhiteSpace(" ".to_string()),
+            span: Span::inserted(),
+        });
+        item.prepend(Chunk {
+            data: ChunkData::Word(marker),
+            span: Span::inserted(),
+        });
+        if pad > 0 {
+            item.prepend(Chunk {
+                data: ChunkData::WhiteSpace(" ".repeat(pad)),
+                span: Span::inserted(),
+            });
+        }
+
+        if let Some(tag) = item.chunk().borrow_mut().tag_mut() {
+            tag.set_attribute("hang", (base + width + 1).to_string());
+        }
+    }
+}
+
+/// Replaces the last `1`, `a`, `A`, `i` or `I` in `marker` with `n`, so that
+/// a label such as `Part 1.` keeps its text. Letters and roman numerals have
+/// no zero, which is written as a decimal.
+fn counter(marker: &str, n: usize) -> String {
+    let Some(i) = marker.rfind(['1', 'a', 'A', 'i', 'I']) else {
+        return marker.to_string();
+    };
+
+    let number = match &marker[i..i + 1] {
+        _ if n == 0 => n.to_string(),
+        "1" => n.to_string(),
+        "a" => alphabetic(n),
+        "A" => alphabetic(n).to_uppercase(),
+        "i" => roman(n),
+        _ => roman(n).to_uppercase(),
+    };
+    format!("{}{number}{}", &marker[..i], &marker[i + 1..])
+}
+
+/// Returns `n` as `a`, `b`, ..., `z`, `aa`, `ab`, ...
+fn alphabetic(mut n: usize) -> String {
+    let mut s = Vec::new();
+    while n > 0 {
+        n -= 1;
+        s.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    s.reverse();
+    String::from_utf8(s).unwrap()
+}
+
+/// Returns `n` as a lowercase roman numeral.
+fn roman(mut n: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+
+    let mut s = String::new();
+    for (value, numeral) in NUMERALS {
+        while n >= value {
+            s.push_str(numeral);
+            n -= value;
+        }
+    }
+    s
+}
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, rc::Rc};
 
 use crate::{
-    Fragment, FragmentType, WordParser,
+    Fragment, FragmentType, RenderOptions, WordParser,
     builtin::{BUILTIN_STYLES, BUILTIN_TAGS},
     common::Span,
     parser::{
@@ -10,54 +10,145 @@ use crate::{
         tag_parer::tag::{Tag, TagType},
     },
     splitter::is_whitespace,
+    term::{Graphics, Icons},
 };
 use document::{Document, Node};
 
+mod align;
+mod chart;
+mod control;
+mod diff;
 pub mod document;
+mod highlight;
+mod image;
+mod layout;
+mod link;
+mod list;
+mod panel;
+mod pre;
+mod rule;
+mod table;
+mod widget;
 
 #[doc(hidden)]
 pub struct Resolver {
     ansi_only: bool,
-    tables: Vec<Rc<Node>>,
+    width: Option<usize>,
+    hyperlinks: bool,
+    controls: bool,
+    graphics: Graphics,
+    icons: Icons,
+    /// Tables, boxes, rules, spacers, `pre`, `code`, `diff`, widgets, charts,
+    /// control elements, images and aligned elements, which are laid out as a
+    /// whole.
+    blocks: Vec<Rc<Node>>,
+    lists: Vec<Rc<Node>>,
+    codes: Vec<Rc<Node>>,
+    links: Vec<Rc<Node>>,
 }
 
 impl Resolver {
     pub fn new(ansi_only: bool) -> Self {
         Self {
             ansi_only,
-            tables: Vec::with_capacity(16),
+            width: None,
+            hyperlinks: true,
+            controls: true,
+            graphics: Graphics::Blocks,
+            icons: Icons::None,
+            blocks: Vec::with_capacity(16),
+            lists: Vec::with_capacity(16),
+            codes: Vec::with_capacity(16),
+            links: Vec::with_capacity(16),
         }
     }
 
+    /// Sets the width text in `p` and `div` elements is wrapped at and
+    /// tables are fitted into, usually [`terminal_width`](crate::terminal_width).
+    /// Elements with a `width` attribute are wrapped even without it.
+    pub fn set_width(&mut self, width: Option<usize>) {
+        self.width = width;
+    }
+
+    /// Sets whether links are written as OSC 8 hyperlinks, which is the
+    /// default, or as their text followed by their target for terminals
+    /// without hyperlinks.
+    pub fn set_hyperlinks(&mut self, hyperlinks: bool) {
+        self.hyperlinks = hyperlinks;
+    }
+
+    /// Sets whether control elements such as `cursor` and `bell` write their
+    /// control sequences, which is the default, or are dropped for output
+    /// which does not go to a terminal.
+    pub fn set_controls(&mut self, controls: bool) {
+        self.controls = controls;
+    }
+
+    /// Sets how `img` elements are shown, usually [`Graphics::detect`]. The
+    /// default is half blocks, which every terminal with colors can show.
+    pub fn set_graphics(&mut self, graphics: Graphics) {
+        self.graphics = graphics;
+    }
+
+    /// Sets how `:name:` shortcodes in text are expanded, usually
+    /// [`Icons::detect`]. They are left as they are by default, and so are
+    /// shortcodes in `pre`, `code` and `diff`.
+    pub fn set_icons(&mut self, icons: Icons) {
+        self.icons = icons;
+    }
+
+    /// Sets the width, hyperlinks, controls, graphics and icons of
+    /// `options`. Colors are left to the caller, see
+    /// [`Node::downgrade_colors`].
+    pub fn set_options(&mut self, options: &RenderOptions) {
+        self.width = options.width;
+        self.hyperlinks = options.hyperlinks;
+        self.controls = options.controls;
+        self.graphics = options.graphics;
+        self.icons = options.icons;
+    }
+
     pub fn resolve(&mut self, chunks: Vec<Chunk>) -> crate::Result<Rc<Document>> {
+        self.resolve_tree(Resolver::tree(chunks))
+    }
+
+    /// Builds the unresolved document of `chunks`, which can be changed with
+    /// the checked methods of [`Node`] before it is resolved with
+    /// [`resolve_tree`](Resolver::resolve_tree).
+    pub fn tree(chunks: Vec<Chunk>) -> Rc<Document> {
         let tree = Document::new();
         let mut node = tree.root();
 
-        for chunk in chunks.iter() {
+        for chunk in chunks {
             match &chunk.data {
                 ChunkData::Tag(tag) => match tag.r#type {
                     TagType::Open => {
-                        node = node.append(chunk.clone());
+                        node = node.append(chunk);
                     }
 
                     TagType::Close => {
-                        node.append(chunk.clone());
+                        node.append(chunk);
                         node = node.parent().unwrap();
                     }
 
                     TagType::SelfClose => {
-                        node.append(chunk.clone());
+                        node.append(chunk);
                     }
                 },
                 ChunkData::WhiteSpace(_) => {
-                    node.append(chunk.clone());
+                    node.append(chunk);
                 }
                 ChunkData::Word(_) => {
-                    node.append(chunk.clone());
+                    node.append(chunk);
                 }
             }
         }
 
+        tree
+    }
+
+    /// Resolves a document built by [`tree`](Resolver::tree).
+    pub fn resolve_tree(&mut self, tree: Rc<Document>) -> crate::Result<Rc<Document>> {
         let node = tree.root();
 
         if self.ansi_only {
@@ -66,7 +157,8 @@ impl Resolver {
         }
 
         {
-            let word_parser = WordParser::new();
+            let mut word_parser = WordParser::new();
+            word_parser.set_icons(self.icons);
             let mut resolved = Vec::with_capacity(128);
             self.parse_words(&node, &word_parser, &mut resolved)?;
             for (node, chunks) in resolved {
@@ -77,9 +169,18 @@ impl Resolver {
             }
         }
 
+        self.highlight();
+
         let mut detachables = Vec::with_capacity(128);
         {
             let mut bindings: HashMap<String, Tag> = HashMap::new();
+            if !self.codes.is_empty() {
+                // bindings of the root, so that any `<let>` takes precedence
+                for (name, source) in highlight::THEME {
+                    let tag = Tag::parse(source).unwrap();
+                    bindings.insert(format!("{}/{name}", node.id()), tag);
+                }
+            }
             Resolver::resolve_bindings(&mut bindings, &node, &mut detachables);
             for node in detachables.drain(..) {
                 node.detach(true);
@@ -93,10 +194,13 @@ impl Resolver {
             }
         }
 
+        self.set_lists();
+        self.set_links();
         Resolver::_resolve(&node, "$root");
+        self.set_blocks();
         Resolver::optimize_styles(&node);
         Resolver::optimize_ansi(&node);
-        self.set_tables();
+        self.wrap(&node);
 
         Ok(tree)
     }
@@ -113,7 +217,7 @@ impl Resolver {
                 let word = child_chunk.word().unwrap();
                 let chs = word_parser.parse(Fragment {
                     r#type: FragmentType::Word,
-                    lexeme: word.clone(),
+                    lexeme: word,
                     span: child_chunk.span,
                 })?;
                 resolved.push((child.clone(), chs));
@@ -121,19 +225,61 @@ impl Resolver {
                 let tag = child_chunk.tag().unwrap();
                 if tag.r#type == TagType::Open {
                     let name = tag.name();
-                    if matches!(name.as_str(), "pre" | "a" | "script" | "style") {
+                    if name == "a" {
+                        self.links.push(child.clone());
+                    } else if matches!(name.as_str(), "script" | "style") {
                         continue;
-                    } else if name == "table" {
-                        self.tables.push(child.clone());
-                    } else if name == "td" {
-                        child.insert_before(Chunk {
-                            data: ChunkData::WhiteSpace(String::new()),
-                            span: Span::inserted(),
-                        });
+                    } else if matches!(
+                        name.as_str(),
+                        "table"
+                            | "box"
+                            | "hr"
+                            | "space"
+                            | "pre"
+                            | "code"
+                            | "diff"
+                            | "progress"
+                            | "spinner"
+                            | "title"
+                            | "img"
+                            | "sparkline"
+                            | "bar"
+                    ) || align::is_aligned(tag)
+                    {
+                        self.blocks.push(child.clone());
+                    } else if matches!(name.as_str(), "ul" | "ol") {
+                        self.lists.push(child.clone());
+                    }
+                    if matches!(name.as_str(), "code" | "diff") {
+                        self.codes.push(child.clone());
                     }
+                } else if tag.r#type == TagType::SelfClose
+                    && matches!(
+                        tag.name().as_str(),
+                        "hr" | "space"
+                            | "progress"
+                            | "spinner"
+                            | "cursor"
+                            | "erase"
+                            | "title"
+                            | "bell"
+                            | "screen"
+                            | "img"
+                            | "sparkline"
+                            | "bar"
+                    )
+                {
+                    self.blocks.push(child.clone());
                 }
 
-                self.parse_words(&child, word_parser, resolved)?;
+                if is_verbatim(tag) {
+                    // shortcodes in `pre`, `code` and `diff` are kept as they are
+                    let mut word_parser = word_parser.clone();
+                    word_parser.set_icons(Icons::None);
+                    self.parse_words(&child, &word_parser, resolved)?;
+                } else {
+                    self.parse_words(&child, word_parser, resolved)?;
+                }
                 continue;
             }
         }
@@ -141,77 +287,28 @@ impl Resolver {
         Ok(())
     }
 
-    fn set_tables(&self) {
-        for table in &self.tables {
-            let indent = table
-                .chunk()
-                .borrow_mut()
-                .tag_mut()
-                .unwrap()
-                .custom()
-                .parse()
-                .unwrap_or(0);
-            let indent = " ".repeat(indent);
-
-            let mut widths = Vec::with_capacity(16);
-            let mut _table: Vec<Vec<(Rc<Node>, usize)>> = Vec::with_capacity(16);
-
-            let mut x = 0;
-            for tr in table.children() {
-                if tr.chunk().borrow().is_ws() {
-                    continue;
-                }
-
-                _table.push(Vec::with_capacity(16));
-
-                let mut y = 0;
-                for td in tr.children() {
-                    if td.chunk().borrow().is_ws() {
-                        td.chunk().borrow_mut().data = ChunkData::WhiteSpace(" ".to_string());
-                        continue;
-                    }
-                    let mut len = 0;
-                    td.word_len(&mut len);
-                    if let Some(prev_len) = widths.get(y) {
-                        if len > *prev_len {
-                            widths[y] = len;
-                        }
-                    } else {
-                        widths.push(len);
-                    }
-                    _table[x].push((td.clone(), len));
-
-                    y += 1
-                }
-
-                if let Some(first) = tr.first_child() {
-                    first.insert_before(Chunk {
-                        data: ChunkData::WhiteSpace(indent.clone()),
-                        span: Span::inserted(),
-                    });
-                }
-
-                x += 1
-            }
-
-            for row in _table {
-                for (i, (col, width)) in row.iter().enumerate() {
-                    let lwidth = &widths[i];
-                    if lwidth == width {
-                        continue;
-                    }
-
-                    let indent = " ".repeat(lwidth - width);
-                    if let Some(last) = col.last_child() {
-                        last.insert_after(Chunk {
-                            data: ChunkData::WhiteSpace(indent.clone()),
-                            span: Span::inserted(),
-                        });
-                    }
-                }
+    /// Lays out all tables, boxes, rules, spacers, `pre`, `code`, `diff`,
+    /// widgets, charts, control elements, images and aligned elements found
+    /// while parsing words. Nested ones are laid out first, so that the elements
+    /// they are part of measure the result.
+    fn set_blocks(&self) {
+        for node in self.blocks.iter().rev() {
+            let name = node.chunk().borrow().tag().unwrap().name().clone();
+            match name.as_str() {
+                "table" => self.set_table(node),
+                "box" => self.set_box(node),
+                "hr" => self.set_rule(node),
+                "space" => self.set_space(node),
+                "pre" | "code" | "diff" => self.set_pre(node),
+                "progress" => self.set_progress(node),
+                "spinner" => self.set_spinner(node),
+                "sparkline" => self.set_sparkline(node),
+                "bar" => self.set_bar(node),
+                "cursor" | "erase" | "title" | "bell" | "screen" => self.set_control(node),
+                "img" => self.set_image(node),
+                _ => self.set_aligned(node),
             }
         }
-        // self.tables.clear();
     }
 
     /// Resolve all declared bindings: <let />
@@ -288,7 +385,12 @@ impl Resolver {
                     child_chunk.data = ChunkData::WhiteSpace(" ".to_string());
                 }
 
-                if let Some(first) = child.next_sibling().and_then(|next| next.first_child()) {
+                // the text of `pre` and `code` is kept as it is
+                if let Some(first) = child
+                    .next_sibling()
+                    .filter(|next| !next.chunk().borrow().is_tag_and(is_verbatim))
+                    .and_then(|next| next.first_child())
+                {
                     if first.chunk().borrow().is_ws() {
                         detachables.push(first);
                     }
@@ -297,43 +399,46 @@ impl Resolver {
                         .borrow()
                         .is_tag_and(|tag| tag.r#type == TagType::Close)
                 }) {
-                    if let Some(next) = node.next_sibling() {
-                        if next.chunk().borrow().is_ws() {
-                            if child.next_sibling().is_some_and(|node| {
-                                node.chunk().borrow().is_tag_and(|tag| tag.name() == "td")
-                            }) {
-                                detachables.push(child.clone());
-                            } else {
-                                detachables.push(next);
-                            }
+                    if let Some(next) = node.next_sibling()
+                        && next.chunk().borrow().is_ws()
+                    {
+                        if child.next_sibling().is_some_and(|node| {
+                            node.chunk().borrow().is_tag_and(|tag| tag.name() == "td")
+                        }) {
+                            detachables.push(child.clone());
+                        } else {
+                            detachables.push(next);
                         }
                     }
-                } else if let Some(next) = child.next_sibling() {
-                    if next.chunk().borrow().is_ws() {
-                        detachables.push(next);
-                    }
+                } else if let Some(next) = child.next_sibling()
+                    && next.chunk().borrow().is_ws()
+                {
+                    detachables.push(next);
                 }
             } else if child_chunk.is_tag() {
                 let name = child_chunk.tag().unwrap().name();
-                if matches!(name.as_str(), "p" | "ziyy" | "$root" | "div") {
-                    if let Some(first) = child.first_child() {
-                        if first.chunk().borrow().is_ws() {
-                            detachables.push(first);
-                        }
+                if matches!(
+                    name.as_str(),
+                    "p" | "ziyy" | "$root" | "div" | "ul" | "ol" | "li" | "box"
+                ) {
+                    if let Some(first) = child.first_child()
+                        && first.chunk().borrow().is_ws()
+                    {
+                        detachables.push(first);
                     }
                 } else if name == "br" {
-                    if let Some(prev) = child.prev_sibling() {
-                        if prev.chunk().borrow().is_ws() {
-                            detachables.push(prev);
-                        }
+                    if let Some(prev) = child.prev_sibling()
+                        && prev.chunk().borrow().is_ws()
+                    {
+                        detachables.push(prev);
                     }
 
-                    if let Some(next) = child.next_sibling() {
-                        if next.chunk().borrow().is_ws() {
-                            detachables.push(next);
-                        }
+                    if let Some(next) = child.next_sibling()
+                        && next.chunk().borrow().is_ws()
+                    {
+                        detachables.push(next);
                     }
-                } else if matches!(name.as_str(), "pre") {
+                } else if is_verbatim(child_chunk.tag().unwrap()) {
                     continue;
                 }
             }
@@ -349,10 +454,36 @@ impl Resolver {
                 let tag = child_chunk.tag_mut().unwrap();
                 if tag.r#type == TagType::Open {
                     let name = tag.name();
-                    if matches!(name.as_str(), "ziyy" | "p" | "div" | "pre" | "table" | "tr") {
+                    if matches!(
+                        name.as_str(),
+                        "ziyy"
+                            | "p"
+                            | "div"
+                            | "pre"
+                            | "code"
+                            | "diff"
+                            | "table"
+                            | "tr"
+                            | "ul"
+                            | "ol"
+                            | "li"
+                            | "box"
+                    ) {
                         if matches!(
                             node_name,
-                            "ziyy" | "$root" | "p" | "div" | "pre" | "table" | "tr"
+                            "ziyy"
+                                | "$root"
+                                | "p"
+                                | "div"
+                                | "pre"
+                                | "code"
+                                | "diff"
+                                | "table"
+                                | "tr"
+                                | "ul"
+                                | "ol"
+                                | "li"
+                                | "box"
                         ) && node
                             .first_child()
                             .is_some_and(|first| first.id() == child.id())
@@ -363,10 +494,6 @@ impl Resolver {
                                 span: Span::inserted(),
                             });
                         }
-                    } else if name == "a" {
-                        for grand_child in child.children() {
-                            grand_child.strip_styles();
-                        }
                     }
 
                     if let Some(last) = child.last_child() {
@@ -439,19 +566,23 @@ impl Resolver {
         while i < decendants.len() {
             let first = &decendants[i];
             let mut first_chunk = first.chunk().borrow_mut();
-            if first_chunk.is_tag() {
-                if let Some(second) = decendants.get(i + 1) {
-                    let mut second_chunk = second.chunk().borrow_mut();
-                    if second_chunk.is_tag() {
-                        let first_tag = first_chunk.tag_mut().unwrap();
-                        let second_tag = second_chunk.tag_mut().unwrap();
-
-                        *second_tag = first_tag.clone() + second_tag.clone();
-                        first_tag.reset_styles();
-                    }
+            if first_chunk.is_tag()
+                && let Some(second) = decendants.get(i + 1)
+            {
+                let mut second_chunk = second.chunk().borrow_mut();
+                if second_chunk.is_tag() {
+                    let first_tag = first_chunk.tag_mut().unwrap();
+                    let second_tag = second_chunk.tag_mut().unwrap();
+
+                    *second_tag = first_tag.clone() + second_tag.clone();
+                    first_tag.reset_styles();
                 }
             }
             i += 1;
         }
     }
 }
+
+fn is_verbatim(tag: &Tag) -> bool {
+    matches!(tag.name().as_str(), "pre" | "code" | "diff")
+}
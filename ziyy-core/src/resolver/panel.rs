@@ -0,0 +1,200 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{Atom, Border, break_lines, build_line, close_element, flatten, word, ws},
+};
+use crate::{
+    parser::tag_parer::tag::{Tag, TagType},
+    width::{display_width, grapheme_width, graphemes},
+};
+
+impl Resolver {
+    /// Draws a box around the content of `node`.
+    ///
+    /// The `box` element takes an `indent`, a `border` of `single`, `double`,
+    /// `rounded`, `ascii` or `none`, a `title` shown in the top border, its
+    /// outer `width` and a `padding` of one value or a vertical and a
+    /// horizontal value. Without a `width`, a box is as wide as its content.
+    /// Every line is filled to the full width, so a background color of the
+    /// box covers it entirely.
+    pub(super) fn set_box(&self, node: &Rc<Node>) {
+        let (indent, border, padding, title, width) = {
+            let chunk = node.chunk().borrow();
+            let tag = chunk.tag().unwrap();
+            let (indent, border, padding) = frame(tag);
+            (
+                indent,
+                border,
+                padding,
+                tag.attribute("title").map(str::to_string),
+                tag.usize_attribute("width"),
+            )
+        };
+        let (vertical, horizontal) = padding;
+
+        let mut atoms = Vec::new();
+        flatten(node, &mut Vec::new(), &mut atoms);
+
+        // the columns taken by borders and padding
+        let frame = 2 * border.is_some() as usize + 2 * horizontal;
+        let width = width.unwrap_or_else(|| {
+            let natural = break_lines(&atoms, None, 0)
+                .iter()
+                .map(|line| line.iter().map(Atom::width).sum())
+                .max()
+                .unwrap_or(0);
+            let title = match (&title, border) {
+                (Some(title), Some(_)) => display_width(title) + 6,
+                _ => 0,
+            };
+            (natural + frame).max(title)
+        });
+        let inner = self.box_width(indent, width).saturating_sub(frame).max(1);
+        let lines = break_lines(&atoms, Some(inner), 0);
+
+        // the tag of every line, which carries the styles of the box so that
+        // they do not run past the end of a line
+        let tag = {
+            let mut chunk = node.chunk().borrow_mut();
+            let line = chunk.clone();
+            chunk.tag_mut().unwrap().reset_styles();
+            line
+        };
+
+        for child in node.children().collect::<Vec<_>>() {
+            if !child
+                .chunk()
+                .borrow()
+                .is_tag_and(|tag| tag.r#type == TagType::Close)
+            {
+                child.discard();
+            }
+        }
+        let close = node.last_child();
+        let mut first = true;
+        let mut line = |build: &dyn Fn(&Rc<Node>)| {
+            let append = |chunk| match &close {
+                Some(close) => close.insert_before(chunk),
+                None => node.append(chunk),
+            };
+            if !first {
+                append(ws("\n".to_string()));
+            }
+            first = false;
+            if indent > 0 {
+                append(ws(" ".repeat(indent)));
+            }
+            let element = append(tag.clone());
+            build(&element);
+            close_element(&element);
+        };
+
+        let padded = |element: &Rc<Node>, content: &[Atom]| {
+            let fill = inner.saturating_sub(content.iter().map(Atom::width).sum());
+            if let Some(border) = border {
+                element.append(word(border.vertical.to_string()));
+            }
+            if horizontal > 0 {
+                element.append(ws(" ".repeat(horizontal)));
+            }
+            build_line(element, content);
+            if fill + horizontal > 0 {
+                element.append(ws(" ".repeat(fill + horizontal)));
+            }
+            if let Some(border) = border {
+                element.append(word(border.vertical.to_string()));
+            }
+        };
+
+        if let Some(border) = border {
+            line(&|element| {
+                element.append(word(top(border, title.as_deref(), inner + 2 * horizontal)));
+            });
+        }
+        for _ in 0..vertical {
+            line(&|element| padded(element, &[]));
+        }
+        for content in &lines {
+            line(&|element| padded(element, content));
+        }
+        for _ in 0..vertical {
+            line(&|element| padded(element, &[]));
+        }
+        if let Some(border) = border {
+            line(&|element| {
+                let mut rule = border.joints[2][0].to_string();
+                rule.extend((0..inner + 2 * horizontal).map(|_| border.horizontal));
+                rule.push(border.joints[2][2]);
+                element.append(word(rule));
+            });
+        }
+    }
+
+    /// Returns the outer width of a box of `width` columns at `indent`, which
+    /// never runs past the resolver width.
+    fn box_width(&self, indent: usize, width: usize) -> usize {
+        match self.width {
+            Some(limit) => width.min(limit.saturating_sub(indent)),
+            None => width,
+        }
+    }
+
+    /// Returns the columns left for the content of the box `tag` when the
+    /// content is as wide as it may be.
+    pub(super) fn box_inner_width(&self, tag: &Tag, default: usize) -> usize {
+        let (indent, border, (_, horizontal)) = frame(tag);
+        let width = tag
+            .usize_attribute("width")
+            .unwrap_or(self.width.unwrap_or(default));
+        let frame = 2 * border.is_some() as usize + 2 * horizontal;
+        self.box_width(indent, width).saturating_sub(frame).max(1)
+    }
+}
+
+/// Returns the indent, the border and the vertical and horizontal padding of
+/// the box `tag`.
+fn frame(tag: &Tag) -> (usize, Option<Border>, (usize, usize)) {
+    let border = match tag.attribute("border") {
+        Some("none") => None,
+        Some(name) => Border::parse(name).or(Border::parse("single")),
+        None => Border::parse("single"),
+    };
+    let padding: Vec<usize> = tag
+        .attribute("padding")
+        .unwrap_or_default()
+        .split([' ', ','])
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    let padding = match padding[..] {
+        [] => (0, 1),
+        [all] => (all, all),
+        [vertical, horizontal, ..] => (vertical, horizontal),
+    };
+    (tag.custom().parse().unwrap_or(0), border, padding)
+}
+
+/// Returns the top border of a box with `width` columns between its corners,
+/// with `title` shown after the first corner.
+fn top(border: Border, title: Option<&str>, width: usize) -> String {
+    let mut rule = border.joints[0][0].to_string();
+    let mut taken = 0;
+    if let Some(title) = title.filter(|_| width >= 4) {
+        rule.push(border.horizontal);
+        rule.push(' ');
+        taken = 3;
+        for grapheme in graphemes(title) {
+            let len = grapheme_width(grapheme);
+            if taken + len + 1 > width {
+                break;
+            }
+            rule.push_str(grapheme);
+            taken += len;
+        }
+        rule.push(' ');
+    }
+    rule.extend((taken..width).map(|_| border.horizontal));
+    rule.push(border.joints[0][2]);
+    rule
+}
@@ -0,0 +1,174 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{close_element, word, ws},
+};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::Tag,
+    },
+    width::display_width,
+};
+
+impl Resolver {
+    /// Prepares the verbatim text of a `pre`, `code` or `diff` element.
+    ///
+    /// A newline right after the opening tag is dropped, tabs are expanded
+    /// to the next multiple of `tabsize` when one is given, and `numbers`
+    /// draws a gutter with line numbers starting at its value or at 1.
+    /// Highlighted blocks also drop the newline before their closing tag.
+    pub(super) fn set_pre(&self, node: &Rc<Node>) {
+        let (verbatim, tabsize, numbers) = {
+            let chunk = node.chunk().borrow();
+            let tag = chunk.tag().unwrap();
+            (
+                tag.name() == "pre",
+                tag.usize_attribute("tabsize").filter(|&n| n > 0),
+                tag.attribute("numbers").map(|n| n.parse().unwrap_or(1)),
+            )
+        };
+
+        let texts: Vec<Rc<Node>> = node
+            .descendants()
+            .skip(1)
+            .filter(|node| {
+                let chunk = node.chunk().borrow();
+                !chunk.is_tag() || chunk.is_tag_and(|tag| tag.name() == "br")
+            })
+            .collect();
+
+        if let Some(first) = texts.first() {
+            let mut chunk = first.chunk().borrow_mut();
+            if let ChunkData::WhiteSpace(s) = &mut chunk.data {
+                if s.starts_with("\r\n") {
+                    s.drain(..2);
+                } else if s.starts_with('\n') {
+                    s.drain(..1);
+                }
+            }
+        }
+
+        // the indentation of the closing tag goes with the last newline
+        if let Some(last) = texts.last().filter(|_| !verbatim) {
+            let mut chunk = last.chunk().borrow_mut();
+            if let ChunkData::WhiteSpace(s) = &mut chunk.data
+                && let Some(i) = s.rfind('\n')
+                && s[i + 1..].chars().all(|c| c == ' ' || c == '\t')
+            {
+                s.truncate(i);
+                if s.ends_with('\r') {
+                    s.pop();
+                }
+            }
+        }
+
+        if let Some(tabsize) = tabsize {
+            let mut column = 0;
+            for text in &texts {
+                let mut chunk = text.chunk().borrow_mut();
+                match &mut chunk.data {
+                    ChunkData::WhiteSpace(s) => {
+                        let mut expanded = String::with_capacity(s.len());
+                        for c in s.chars() {
+                            match c {
+                                '\t' => {
+                                    let n = tabsize - column % tabsize;
+                                    expanded.extend((0..n).map(|_| ' '));
+                                    column += n;
+                                }
+                                '\n' => {
+                                    expanded.push(c);
+                                    column = 0;
+                                }
+                                '\r' => expanded.push(c),
+                                _ => {
+                                    expanded.push(c);
+                                    column += 1;
+                                }
+                            }
+                        }
+                        *s = expanded;
+                    }
+                    ChunkData::Word(s) => column += display_width(s),
+                    ChunkData::Tag(_) => column = 0,
+                }
+            }
+        }
+
+        if let Some(start) = numbers {
+            number_lines(node, &texts, start);
+        }
+    }
+}
+
+/// Inserts a gutter with the line number at the start of every line.
+fn number_lines(node: &Rc<Node>, texts: &[Rc<Node>], start: usize) {
+    // whether the text ends with a line break, which does not start a line
+    let mut empty = false;
+    // the nodes each line after the first starts after
+    let mut breaks = Vec::new();
+    for text in texts {
+        let chunk = text.chunk().borrow();
+        match &chunk.data {
+            ChunkData::WhiteSpace(s) if s.contains('\n') => {
+                let segments: Vec<String> = s.split_inclusive('\n').map(str::to_string).collect();
+                drop(chunk);
+                let mut last = text.clone();
+                for (i, segment) in segments.into_iter().enumerate() {
+                    let ends = segment.ends_with('\n');
+                    if i == 0 {
+                        text.chunk().borrow_mut().data = ChunkData::WhiteSpace(segment);
+                    } else {
+                        last = last.insert_after(ws(segment));
+                    }
+                    if ends {
+                        breaks.push(last.clone());
+                    }
+                }
+                empty = true;
+            }
+            ChunkData::WhiteSpace(_) => {}
+            ChunkData::Word(_) => empty = false,
+            ChunkData::Tag(tag) => {
+                // every line break of a `br` gets a gutter of its own
+                let n = tag.custom().parse().unwrap_or(1);
+                drop(chunk);
+                text.chunk()
+                    .borrow_mut()
+                    .tag_mut()
+                    .unwrap()
+                    .set_custom("1".to_string());
+                let mut last = text.clone();
+                breaks.push(last.clone());
+                for _ in 1..n {
+                    last = last.insert_after(text.chunk().borrow().clone());
+                    breaks.push(last.clone());
+                }
+                empty = true;
+            }
+        }
+    }
+    if empty {
+        breaks.pop();
+    }
+
+    let width = (start + breaks.len()).to_string().len();
+    let gutter = |n: usize, element: Rc<Node>| {
+        element.append(word(format!("{:>width$} │", start + n)));
+        close_element(&element);
+        element.insert_after(ws(" ".to_string()));
+    };
+    let tag = || Chunk {
+        data: ChunkData::Tag(Tag::open("d")),
+        span: Span::inserted(),
+    };
+
+    gutter(0, node.prepend(tag()));
+    for (i, br) in breaks.iter().enumerate() {
+        gutter(i + 1, br.insert_after(tag()));
+    }
+}
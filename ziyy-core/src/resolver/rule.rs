@@ -0,0 +1,157 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{close_element, word, ws},
+};
+use crate::{
+    parser::{chunk::ChunkData, tag_parer::tag::TagType},
+    width::{display_width, grapheme_width, graphemes},
+};
+
+/// Width of rules when neither the rule nor the resolver has one.
+const DEFAULT_WIDTH: usize = 80;
+
+impl Resolver {
+    /// Draws a horizontal rule on a line of its own.
+    ///
+    /// The `hr` element takes the `char` it is drawn with or a `style` of
+    /// `single`, `double`, `heavy`, `dashed`, `dotted` or `ascii`, a `label`
+    /// centered on the rule and a `width`, which defaults to the width left
+    /// inside the enclosing box, list item or indented paragraph.
+    pub(super) fn set_rule(&self, node: &Rc<Node>) {
+        let (start, room) = self.room(node);
+        let (pattern, label, width) = {
+            let mut chunk = node.chunk().borrow_mut();
+            let tag = chunk.tag_mut().unwrap();
+            tag.r#type = TagType::Open;
+            let style = match tag.attribute("style") {
+                Some("double") => "═",
+                Some("heavy") => "━",
+                Some("dashed") => "╌",
+                Some("dotted") => "┄",
+                Some("ascii") => "-",
+                _ => "─",
+            };
+            (
+                tag.attribute("char")
+                    .filter(|s| display_width(s) > 0)
+                    .unwrap_or(style)
+                    .to_string(),
+                tag.attribute("label").map(|label| format!(" {label} ")),
+                tag.usize_attribute("width").unwrap_or(room),
+            )
+        };
+
+        let rule = match label {
+            Some(label) if display_width(&label) < width => {
+                let fill = width - display_width(&label);
+                let mut rule = repeat(&pattern, fill / 2);
+                rule.push_str(&label);
+                rule.push_str(&repeat(&pattern, fill - fill / 2));
+                rule
+            }
+            _ => repeat(&pattern, width),
+        };
+
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+        node.append(word(rule));
+        close_element(node);
+
+        // a rule never shares its line with the text around it, and the lines
+        // it breaks keep the indentation of the enclosing block
+        let newline = format!("\n{}", " ".repeat(start));
+        if let Some(prev) = node.prev_sibling() {
+            let mut chunk = prev.chunk().borrow_mut();
+            match &chunk.data {
+                ChunkData::WhiteSpace(s) if s.contains('\n') => {}
+                ChunkData::WhiteSpace(_) => chunk.data = ChunkData::WhiteSpace(newline.clone()),
+                _ => {
+                    drop(chunk);
+                    node.insert_before(ws(newline.clone()));
+                }
+            }
+        }
+        if let Some(next) = node.next_sibling() {
+            let mut chunk = next.chunk().borrow_mut();
+            match &chunk.data {
+                ChunkData::WhiteSpace(s) if s.starts_with('\n') => {}
+                ChunkData::WhiteSpace(_) => chunk.data = ChunkData::WhiteSpace(newline),
+                // the end of the enclosing element
+                ChunkData::Tag(tag) if tag.r#type == TagType::Close => {}
+                _ => {
+                    drop(chunk);
+                    node.insert_after(ws(newline));
+                }
+            }
+        }
+    }
+
+    /// Returns the column the lines around `node` start at and the columns
+    /// left after it, inside the nearest box or else the resolver width.
+    fn room(&self, node: &Rc<Node>) -> (usize, usize) {
+        let mut start = 0;
+        let mut limit = usize::MAX;
+        let mut hung = false;
+        for ancestor in node.ancestors() {
+            let chunk = ancestor.chunk().borrow();
+            let Some(tag) = chunk.tag() else {
+                continue;
+            };
+            match tag.name().as_str() {
+                "box" => {
+                    let width = self.box_inner_width(tag, DEFAULT_WIDTH).min(limit);
+                    return (start, width.saturating_sub(start));
+                }
+                // the hang of the innermost item already holds the outer ones
+                "li" if !hung => {
+                    start += tag.usize_attribute("hang").unwrap_or(0);
+                    hung = true;
+                }
+                "p" | "div" => {
+                    start += tag.custom().parse().unwrap_or(0);
+                    if let Some(width) = tag.usize_attribute("width") {
+                        limit = limit.min(width);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let width = self.width.unwrap_or(DEFAULT_WIDTH).min(limit);
+        (start, width.saturating_sub(start))
+    }
+
+    /// Replaces a `space` element with `n` spaces, which are kept as they
+    /// are by the other passes.
+    pub(super) fn set_space(&self, node: &Rc<Node>) {
+        let n = {
+            let chunk = node.chunk().borrow();
+            chunk.tag().unwrap().custom().parse().unwrap_or(1)
+        };
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+        node.chunk().borrow_mut().data = ChunkData::WhiteSpace(" ".repeat(n));
+    }
+}
+
+/// Repeats the graphemes of `pattern` until they fill `width` columns.
+fn repeat(pattern: &str, width: usize) -> String {
+    let mut s = String::new();
+    let mut taken = 0;
+    for grapheme in graphemes(pattern).cycle() {
+        let len = grapheme_width(grapheme);
+        if len == 0 {
+            continue;
+        }
+        if taken + len > width {
+            break;
+        }
+        s.push_str(grapheme);
+        taken += len;
+    }
+    s
+}
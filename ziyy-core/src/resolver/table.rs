@@ -0,0 +1,625 @@
+use std::rc::Rc;
+
+use super::{Resolver, document::Node};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        tag_parer::tag::TagType,
+    },
+    width::{display_width, grapheme_width, graphemes},
+};
+
+/// Box-drawing characters of a table border.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Border {
+    pub(super) horizontal: char,
+    pub(super) vertical: char,
+    /// Corners and junctions of the top, middle and bottom rules.
+    pub(super) joints: [[char; 3]; 3],
+}
+
+impl Border {
+    pub(super) fn parse(name: &str) -> Option<Self> {
+        let (horizontal, vertical, joints) = match name {
+            "single" => ('─', '│', "┌┬┐├┼┤└┴┘"),
+            "double" => ('═', '║', "╔╦╗╠╬╣╚╩╝"),
+            "rounded" => ('─', '│', "╭┬╮├┼┤╰┴╯"),
+            "ascii" => ('-', '|', "+++++++++"),
+            _ => return None,
+        };
+
+        let mut chars = joints.chars();
+        let mut joints = [[' '; 3]; 3];
+        for joint in joints.iter_mut().flatten() {
+            *joint = chars.next().unwrap();
+        }
+        Some(Border {
+            horizontal,
+            vertical,
+            joints,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+impl Align {
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "left" => Some(Align::Left),
+            "right" => Some(Align::Right),
+            "center" => Some(Align::Center),
+            _ => None,
+        }
+    }
+}
+
+/// A piece of cell content together with the elements it is nested in.
+#[derive(Debug, Clone)]
+pub(super) struct Atom {
+    pub(super) chain: Vec<Rc<Node>>,
+    pub(super) item: Item,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Item {
+    Word(String),
+    Space(String),
+    /// A tag without content, such as an escape sequence.
+    Mark(Chunk),
+    Break,
+}
+
+impl Atom {
+    pub(super) fn width(&self) -> usize {
+        match &self.item {
+            Item::Word(s) | Item::Space(s) => display_width(s),
+            Item::Mark(_) | Item::Break => 0,
+        }
+    }
+
+    fn chunk(&self) -> Chunk {
+        let data = match &self.item {
+            Item::Word(s) => ChunkData::Word(s.clone()),
+            Item::Space(s) => ChunkData::WhiteSpace(s.clone()),
+            Item::Mark(chunk) => return chunk.clone(),
+            Item::Break => unreachable!("line breaks are never part of a line"),
+        };
+        Chunk {
+            data,
+            span: Span::inserted(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Cell {
+    /// The `td` or `th` element, or none for cells missing from a short row.
+    node: Option<Rc<Node>>,
+    span: usize,
+    align: Option<Align>,
+    width: Option<usize>,
+    hang: usize,
+    atoms: Vec<Atom>,
+    lines: Vec<Vec<Atom>>,
+}
+
+#[derive(Debug)]
+struct Row {
+    node: Rc<Node>,
+    cells: Vec<Cell>,
+    header: bool,
+}
+
+impl Row {
+    /// Returns for every column boundary whether a cell starts or ends there.
+    fn boundaries(&self, columns: usize) -> Vec<bool> {
+        let mut boundaries = vec![false; columns + 1];
+        let mut column = 0;
+        for cell in &self.cells {
+            boundaries[column] = true;
+            column += cell.span;
+        }
+        boundaries[columns] = true;
+        boundaries
+    }
+}
+
+impl Resolver {
+    /// Lays out a table.
+    ///
+    /// Rows are made of `td` and `th` cells. The `table` element takes an
+    /// `indent`, a `border` of `single`, `double`, `rounded` or `ascii`, the
+    /// `padding` of its cells and a list of column alignments in `align`.
+    /// Cells take `align`, `colspan`, a maximum `width` and a `hang` for
+    /// wrapped lines. Cells are wrapped when the table is wider than the
+    /// resolver width.
+    pub(super) fn set_table(&self, table: &Rc<Node>) {
+        let (indent, border, padding, aligns) = {
+            let chunk = table.chunk().borrow();
+            let tag = chunk.tag().unwrap();
+            let border = tag.attribute("border").and_then(Border::parse);
+            let padding = tag.usize_attribute("padding");
+            let aligns: Vec<_> = tag
+                .attribute("align")
+                .unwrap_or_default()
+                .split([' ', ','])
+                .filter(|s| !s.is_empty())
+                .map(Align::parse)
+                .collect();
+            (
+                tag.custom().parse().unwrap_or(0),
+                border,
+                padding.unwrap_or(border.is_some() as usize),
+                aligns,
+            )
+        };
+
+        let mut rows = Vec::with_capacity(16);
+        for tr in table.children() {
+            if !tr
+                .chunk()
+                .borrow()
+                .is_tag_and(|tag| tag.r#type == TagType::Open && tag.name() == "tr")
+            {
+                continue;
+            }
+
+            let mut header = false;
+            let mut cells = Vec::with_capacity(16);
+            for td in tr.children() {
+                let chunk = td.chunk().borrow();
+                let Some(tag) = chunk.tag().filter(|tag| {
+                    tag.r#type != TagType::Close && matches!(tag.name().as_str(), "td" | "th")
+                }) else {
+                    continue;
+                };
+
+                header |= tag.name() == "th";
+                let mut atoms = Vec::new();
+                flatten(&td, &mut Vec::new(), &mut atoms);
+                cells.push(Cell {
+                    node: Some(td.clone()),
+                    span: tag.usize_attribute("colspan").unwrap_or(1).max(1),
+                    align: tag.attribute("align").and_then(Align::parse),
+                    width: tag.usize_attribute("width"),
+                    hang: tag.usize_attribute("hang").unwrap_or(0),
+                    atoms,
+                    lines: Vec::new(),
+                });
+            }
+
+            // an empty row has no line to draw
+            if !cells.is_empty() {
+                rows.push(Row {
+                    node: tr.clone(),
+                    cells,
+                    header,
+                });
+            }
+        }
+
+        // a cell spans at most as many columns as the longest row has cells
+        let most = rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+        for cell in rows.iter_mut().flat_map(|row| &mut row.cells) {
+            cell.span = cell.span.min(most);
+        }
+
+        let columns = rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.span).sum())
+            .max()
+            .unwrap_or(0);
+        for row in &mut rows {
+            let missing = columns - row.cells.iter().map(|cell| cell.span).sum::<usize>();
+            row.cells.extend((0..missing).map(|_| Cell {
+                node: None,
+                span: 1,
+                align: None,
+                width: None,
+                hang: 0,
+                atoms: Vec::new(),
+                lines: Vec::new(),
+            }));
+        }
+
+        // cells are separated by a space or a vertical line
+        let sep = 1 + 2 * padding;
+        let widths = column_widths(&rows, columns, sep, self.width, indent, border);
+        for row in &mut rows {
+            let mut column = 0;
+            for cell in &mut row.cells {
+                let width = spanned(&widths, column, cell.span, sep);
+                cell.lines = break_lines(&cell.atoms, Some(width), cell.hang);
+                column += cell.span;
+            }
+        }
+
+        for child in table.children().collect::<Vec<_>>() {
+            if !child
+                .chunk()
+                .borrow()
+                .is_tag_and(|tag| tag.r#type == TagType::Close)
+            {
+                child.discard();
+            }
+        }
+        let close = table.last_child();
+        let append = |chunk: Chunk| match &close {
+            Some(close) => close.insert_before(chunk),
+            None => table.append(chunk),
+        };
+
+        let rule = |above: Option<&Row>, below: Option<&Row>, level: usize| {
+            let border = border.unwrap();
+            let above = above.map(|row| row.boundaries(columns));
+            let below = below.map(|row| row.boundaries(columns));
+            let mut rule = " ".repeat(indent);
+            rule.push(border.joints[level][0]);
+            for (i, width) in widths.iter().enumerate() {
+                rule.extend((0..width + 2 * padding).map(|_| border.horizontal));
+                if i + 1 == columns {
+                    rule.push(border.joints[level][2]);
+                    continue;
+                }
+                let above = above.as_ref().is_some_and(|b| b[i + 1]);
+                let below = below.as_ref().is_some_and(|b| b[i + 1]);
+                rule.push(match (above, below) {
+                    (true, true) => border.joints[1][1],
+                    (true, false) => border.joints[2][1],
+                    (false, true) => border.joints[0][1],
+                    (false, false) => border.horizontal,
+                });
+            }
+            rule
+        };
+
+        if border.is_some() && !rows.is_empty() {
+            append(word(rule(None, rows.first(), 0)));
+            append(ws("\n".to_string()));
+        }
+
+        for (r, row) in rows.iter().enumerate() {
+            if r > 0 {
+                append(ws("\n".to_string()));
+            }
+
+            let tr = append(row.node.chunk().borrow().clone());
+            let height = row.cells.iter().map(|cell| cell.lines.len()).max();
+            for i in 0..height.unwrap_or(0) {
+                if i > 0 {
+                    tr.append(ws("\n".to_string()));
+                }
+                if indent > 0 {
+                    tr.append(ws(" ".repeat(indent)));
+                }
+                if let Some(border) = border {
+                    tr.append(word(border.vertical.to_string()));
+                }
+
+                let mut column = 0;
+                for (c, cell) in row.cells.iter().enumerate() {
+                    let width = spanned(&widths, column, cell.span, sep);
+                    let align = cell
+                        .align
+                        .or_else(|| aligns.get(column).copied().flatten())
+                        .unwrap_or(Align::Left);
+                    column += cell.span;
+
+                    let line = cell.lines.get(i).map(Vec::as_slice).unwrap_or_default();
+                    let fill = width.saturating_sub(line.iter().map(Atom::width).sum());
+                    let (before, mut after) = match align {
+                        Align::Left => (0, fill),
+                        Align::Right => (fill, 0),
+                        Align::Center => (fill / 2, fill - fill / 2),
+                    };
+                    let mut right = padding;
+                    if border.is_none() && c + 1 == row.cells.len() {
+                        // trailing spaces would only push the line past the terminal
+                        if line.is_empty() {
+                            continue;
+                        }
+                        after = 0;
+                        right = 0;
+                    }
+
+                    // padding goes around the cell, so that its styles stay on its text
+                    let left = padding + before + (border.is_none() && c > 0) as usize;
+                    if left > 0 {
+                        tr.append(ws(" ".repeat(left)));
+                    }
+                    match &cell.node {
+                        Some(node) if !line.is_empty() => {
+                            let td = tr.append(node.chunk().borrow().clone());
+                            build_line(&td, line);
+                            close_element(&td);
+                        }
+                        _ => {}
+                    }
+                    if after + right > 0 {
+                        tr.append(ws(" ".repeat(after + right)));
+                    }
+                    if let Some(border) = border {
+                        tr.append(word(border.vertical.to_string()));
+                    }
+                }
+            }
+            close_element(&tr);
+
+            if border.is_some() && row.header && r + 1 < rows.len() {
+                append(ws("\n".to_string()));
+                append(word(rule(Some(row), rows.get(r + 1), 1)));
+            }
+        }
+
+        if border.is_some() && !rows.is_empty() {
+            append(ws("\n".to_string()));
+            append(word(rule(rows.last(), None, 2)));
+        }
+    }
+}
+
+/// Returns the width of every column, shrinking the widest columns until the
+/// table fits into `width`.
+fn column_widths(
+    rows: &[Row],
+    columns: usize,
+    sep: usize,
+    width: Option<usize>,
+    indent: usize,
+    border: Option<Border>,
+) -> Vec<usize> {
+    let natural = |cell: &Cell| {
+        break_lines(&cell.atoms, None, 0)
+            .iter()
+            .map(|line| line.iter().map(Atom::width).sum())
+            .max()
+            .unwrap_or(0)
+    };
+
+    let mut widths = vec![0; columns];
+    let mut limits = vec![usize::MAX; columns];
+    for row in rows {
+        let mut column = 0;
+        for cell in row.cells.iter() {
+            if cell.span == 1 {
+                widths[column] = widths[column].max(natural(cell));
+                if let Some(width) = cell.width {
+                    limits[column] = limits[column].min(width);
+                }
+            }
+            column += cell.span;
+        }
+    }
+    for (width, limit) in widths.iter_mut().zip(&limits) {
+        *width = (*width).min(*limit);
+    }
+
+    for row in rows {
+        let mut column = 0;
+        for cell in row.cells.iter() {
+            if cell.span > 1 {
+                let width = natural(cell).min(cell.width.unwrap_or(usize::MAX));
+                let available = spanned(&widths, column, cell.span, sep);
+                if width > available {
+                    widths[column + cell.span - 1] += width - available;
+                }
+            }
+            column += cell.span;
+        }
+    }
+
+    if let Some(width) = width {
+        let fixed = match border {
+            Some(_) => indent + columns * sep + 1,
+            None => (indent + columns * sep).saturating_sub(1),
+        };
+        let available = width.saturating_sub(fixed);
+        let mut total: usize = widths.iter().sum();
+        while total > available {
+            let (i, &widest) = widths
+                .iter()
+                .enumerate()
+                .max_by_key(|&(i, width)| (width, usize::MAX - i))
+                .unwrap();
+            if widest <= 1 {
+                break;
+            }
+            widths[i] -= 1;
+            total -= 1;
+        }
+    }
+
+    widths
+}
+
+/// Returns the width of a cell spanning `span` columns from `column`.
+fn spanned(widths: &[usize], column: usize, span: usize, sep: usize) -> usize {
+    widths[column..column + span].iter().sum::<usize>() + (span - 1) * sep
+}
+
+/// Collects the content of `node` as atoms.
+pub(super) fn flatten(node: &Rc<Node>, chain: &mut Vec<Rc<Node>>, atoms: &mut Vec<Atom>) {
+    for child in node.children() {
+        let chunk = child.chunk().borrow();
+        let item = match &chunk.data {
+            ChunkData::Word(word) => Item::Word(word.clone()),
+            ChunkData::WhiteSpace(ws) if ws.contains('\n') => Item::Break,
+            ChunkData::WhiteSpace(ws) => Item::Space(ws.clone()),
+            ChunkData::Tag(tag) if tag.name() == "br" => {
+                let n = tag.custom().parse().unwrap_or(1);
+                for _ in 0..n {
+                    atoms.push(Atom {
+                        chain: chain.clone(),
+                        item: Item::Break,
+                    });
+                }
+                continue;
+            }
+            ChunkData::Tag(tag) if tag.r#type == TagType::Close => continue,
+            ChunkData::Tag(_) if child.has_children() => {
+                chain.push(child.clone());
+                flatten(&child, chain, atoms);
+                chain.pop();
+                continue;
+            }
+            ChunkData::Tag(_) => Item::Mark(chunk.clone()),
+        };
+        atoms.push(Atom {
+            chain: chain.clone(),
+            item,
+        });
+    }
+}
+
+/// Breaks atoms into lines no wider than `width`, starting wrapped lines
+/// with `hang` spaces. Words wider than a line are split.
+pub(super) fn break_lines(atoms: &[Atom], width: Option<usize>, hang: usize) -> Vec<Vec<Atom>> {
+    let mut lines = vec![Vec::new()];
+    let mut column = 0;
+    let mut filled = false;
+    // spaces and marks since the last word
+    let mut pending: Vec<Atom> = Vec::new();
+
+    let new_line = |lines: &mut Vec<Vec<Atom>>, pending: &mut Vec<Atom>, hang: usize| {
+        let mut line = Vec::new();
+        if hang > 0 {
+            line.push(Atom {
+                chain: Vec::new(),
+                item: Item::Space(" ".repeat(hang)),
+            });
+        }
+        line.extend(
+            pending
+                .drain(..)
+                .filter(|atom| matches!(atom.item, Item::Mark(_))),
+        );
+        lines.push(line);
+    };
+
+    for atom in atoms {
+        match &atom.item {
+            Item::Break => {
+                new_line(&mut lines, &mut pending, 0);
+                column = 0;
+                filled = false;
+            }
+            Item::Space(_) if !filled => {}
+            Item::Space(_) | Item::Mark(_) => pending.push(atom.clone()),
+            Item::Word(word) => {
+                let space: usize = pending.iter().map(Atom::width).sum();
+                let len = display_width(word);
+                if filled && width.is_some_and(|width| column + space + len > width) {
+                    new_line(&mut lines, &mut pending, hang);
+                    column = hang;
+                } else {
+                    lines.last_mut().unwrap().append(&mut pending);
+                    column += space;
+                }
+
+                let Some(width) = width.filter(|&width| column + len > width) else {
+                    lines.last_mut().unwrap().push(atom.clone());
+                    column += len;
+                    filled = true;
+                    continue;
+                };
+
+                let mut rest = word.as_str();
+                while !rest.is_empty() {
+                    let room = width.saturating_sub(column);
+                    let mut end = 0;
+                    let mut taken = 0;
+                    for grapheme in graphemes(rest) {
+                        let len = grapheme_width(grapheme);
+                        if taken + len > room && (end > 0 || filled) {
+                            break;
+                        }
+                        end += grapheme.len();
+                        taken += len;
+                    }
+
+                    if end > 0 {
+                        lines.last_mut().unwrap().push(Atom {
+                            chain: atom.chain.clone(),
+                            item: Item::Word(rest[..end].to_string()),
+                        });
+                        column += taken;
+                        filled = true;
+                        rest = &rest[end..];
+                    }
+                    if !rest.is_empty() {
+                        new_line(&mut lines, &mut pending, hang);
+                        column = hang;
+                        filled = false;
+                    }
+                }
+            }
+        }
+    }
+
+    lines.last_mut().unwrap().extend(
+        pending
+            .into_iter()
+            .filter(|atom| matches!(atom.item, Item::Mark(_))),
+    );
+    lines
+}
+
+/// Appends the atoms of a line to `parent`, recreating the elements they are
+/// nested in.
+pub(super) fn build_line(parent: &Rc<Node>, line: &[Atom]) {
+    let mut open: Vec<(u32, Rc<Node>)> = Vec::new();
+    for atom in line {
+        let shared = open
+            .iter()
+            .zip(&atom.chain)
+            .take_while(|((id, _), node)| *id == node.id())
+            .count();
+        for (_, node) in open.drain(shared..) {
+            close_element(&node);
+        }
+
+        for node in &atom.chain[shared..] {
+            let element = open
+                .last()
+                .map_or(parent, |(_, node)| node)
+                .append(node.chunk().borrow().clone());
+            open.push((node.id(), element));
+        }
+        open.last()
+            .map_or(parent, |(_, node)| node)
+            .append(atom.chunk());
+    }
+
+    for (_, node) in open {
+        close_element(&node);
+    }
+}
+
+pub(super) fn close_element(node: &Rc<Node>) {
+    let tag = node.chunk().borrow().tag().unwrap().close();
+    node.append(Chunk {
+        data: ChunkData::Tag(tag),
+        span: Span::inserted(),
+    });
+}
+
+pub(super) fn word(text: String) -> Chunk {
+    Chunk {
+        data: ChunkData::Word(text),
+        span: Span::inserted(),
+    }
+}
+
+pub(super) fn ws(text: String) -> Chunk {
+    Chunk {
+        data: ChunkData::WhiteSpace(text),
+        span: Span::inserted(),
+    }
+}
@@ -0,0 +1,165 @@
+use std::rc::Rc;
+
+use super::{
+    Resolver,
+    document::Node,
+    table::{close_element, word},
+};
+use crate::{
+    common::Span,
+    parser::{
+        chunk::{Chunk, ChunkData},
+        color::{Color, Rgb},
+        tag_parer::tag::{Tag, TagType},
+    },
+};
+
+/// Width of progress bars and bars without a `width`.
+const DEFAULT_WIDTH: usize = 20;
+
+/// Most cells a bar may take without a resolver width.
+const MAX_WIDTH: usize = 1000;
+
+/// Glyphs of a cell filled by one to eight eighths.
+const EIGHTHS: [&str; 8] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"];
+
+impl Resolver {
+    /// Draws a progress bar of `width` cells filled to `value` out of `max`.
+    ///
+    /// Partly filled cells are drawn with eighth blocks. A `from` and `to`
+    /// color fade the filled cells from one to the other when both are
+    /// `rgb()` or hex colors, and `percent` shows the percentage after the
+    /// bar. A `value` which is not a number, such as a placeholder, draws an
+    /// empty bar.
+    pub(super) fn set_progress(&self, node: &Rc<Node>) {
+        let (value, max, width, from, to, percent) = {
+            let mut chunk = node.chunk().borrow_mut();
+            let tag = chunk.tag_mut().unwrap();
+            tag.r#type = TagType::Open;
+            let number = |name| {
+                tag.attribute(name)
+                    .and_then(|s| s.trim().parse::<f64>().ok())
+                    .filter(|n| n.is_finite())
+            };
+            let color = |name| {
+                tag.attribute(name)
+                    .and_then(|s| Color::try_from((format!("f{s}"), Span::inserted())).ok())
+            };
+            (
+                number("value").unwrap_or(0.0),
+                number("max").filter(|&n| n > 0.0).unwrap_or(100.0),
+                self.bar_width(tag.usize_attribute("width")),
+                color("from"),
+                color("to"),
+                tag.attribute("percent").is_some(),
+            )
+        };
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+
+        let ratio = (value / max).clamp(0.0, 1.0);
+        let cells = fill(ratio, width);
+        let filled = cells.len();
+
+        match (from, to) {
+            (Some(Color::Rgb(from)), Some(Color::Rgb(to))) => {
+                for (i, cell) in cells.into_iter().enumerate() {
+                    let t = match width {
+                        0 | 1 => 0.0,
+                        _ => i as f64 / (width - 1) as f64,
+                    };
+                    let mut tag = Tag::with_name("c");
+                    tag.r#type = TagType::Open;
+                    tag.set_fg_color(Color::Rgb(mix(&from, &to, t)));
+                    let element = node.append(Chunk {
+                        data: ChunkData::Tag(tag),
+                        span: Span::inserted(),
+                    });
+                    element.append(word(cell.to_string()));
+                    close_element(&element);
+                }
+            }
+            (from, _) => {
+                if filled > 0 {
+                    let mut tag = Tag::with_name("c");
+                    tag.r#type = TagType::Open;
+                    if let Some(from) = from {
+                        tag.set_fg_color(from);
+                    }
+                    let element = node.append(Chunk {
+                        data: ChunkData::Tag(tag),
+                        span: Span::inserted(),
+                    });
+                    element.append(word(cells.concat()));
+                    close_element(&element);
+                }
+            }
+        }
+
+        // the empty cells are words as well, so that the bar never breaks
+        let mut rest = " ".repeat(width - filled);
+        if percent {
+            rest.push_str(&format!(" {:>3}%", (ratio * 100.0).round()));
+        }
+        if !rest.is_empty() {
+            node.append(word(rest));
+        }
+        close_element(node);
+    }
+
+    /// Returns the width of a bar with a `width` attribute of `width`, which
+    /// is never wider than the resolver width.
+    pub(super) fn bar_width(&self, width: Option<usize>) -> usize {
+        width
+            .unwrap_or(DEFAULT_WIDTH)
+            .min(self.width.unwrap_or(MAX_WIDTH))
+    }
+
+    /// Draws frame `frame` of a spinner, counting from 0 and starting over
+    /// after the last frame of its `style`: `dots`, `line`, `arc`, `circle`
+    /// or `bounce`.
+    pub(super) fn set_spinner(&self, node: &Rc<Node>) {
+        let glyph = {
+            let mut chunk = node.chunk().borrow_mut();
+            let tag = chunk.tag_mut().unwrap();
+            tag.r#type = TagType::Open;
+            let frames: &[&str] = match tag.attribute("style") {
+                Some("line") => &["-", "\\", "|", "/"],
+                Some("arc") => &["◜", "◠", "◝", "◞", "◡", "◟"],
+                Some("circle") => &["◐", "◓", "◑", "◒"],
+                Some("bounce") => &["⠁", "⠂", "⠄", "⠂"],
+                _ => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            };
+            let frame = tag.usize_attribute("frame").unwrap_or(0);
+            frames[frame % frames.len()]
+        };
+        for child in node.children().collect::<Vec<_>>() {
+            child.discard();
+        }
+        node.append(word(glyph.to_string()));
+        close_element(node);
+    }
+}
+
+/// Returns the cells of a bar `width` cells wide filled to `ratio`, ending
+/// with a partly filled one.
+pub(super) fn fill(ratio: f64, width: usize) -> Vec<&'static str> {
+    let eighths = (ratio * width.saturating_mul(8) as f64).round() as usize;
+    let mut cells = vec![EIGHTHS[7]; eighths / 8];
+    if let Some(partial) = (eighths % 8).checked_sub(1) {
+        cells.push(EIGHTHS[partial]);
+    }
+    cells
+}
+
+/// Returns the color `t` of the way from `from` to `to`.
+fn mix(from: &Rgb, to: &Rgb, t: f64) -> Rgb {
+    let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Rgb(
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+        from.3,
+    )
+}
@@ -1,9 +1,5 @@
-pub use source::Source;
-
 use crate::common::Span;
 
-mod source;
-
 pub fn is_alpha(c: char) -> bool {
     matches!(c, 'a'..='z' | 'A'..='Z' | '_')
 }
@@ -20,8 +16,17 @@ pub fn is_hexdigit(c: char) -> bool {
     c.is_ascii_hexdigit()
 }
 
-pub trait GenericScanner<T: PartialEq, U> {
-    fn source(&self) -> &impl Source<T>;
+/// Returns the char starting at byte `i` of `source`, or `'\0'` past its end.
+pub fn char_at(source: &str, i: usize) -> char {
+    source
+        .get(i..)
+        .and_then(|s| s.chars().next())
+        .unwrap_or('\0')
+}
+
+/// A scanner over a borrowed `&str`. Positions are byte offsets.
+pub trait GenericScanner<'a, U> {
+    fn source(&self) -> &'a str;
     fn tokens(&mut self) -> &mut Vec<U>;
     fn set_start(&mut self, n: usize);
     fn current(&self) -> usize;
@@ -29,57 +34,45 @@ pub trait GenericScanner<T: PartialEq, U> {
     fn span(&mut self) -> &mut Span;
     fn scan_token(&mut self);
 
-    fn scan_tokens<'a>(&'a mut self) -> Vec<U>
-    where
-        T: 'a,
-    {
+    fn scan_tokens(&mut self) -> Vec<U> {
         while !self.is_at_end() {
             self.set_start(self.current());
+            self.span().tie_end();
             self.scan_token();
         }
 
         std::mem::take(self.tokens())
     }
 
-    fn peek(&self) -> T {
-        if self.is_at_end() {
-            self.source().null()
-        } else {
-            self.source().at(self.current())
-        }
+    fn peek(&self) -> char {
+        char_at(self.source(), self.current())
     }
 
-    fn peek_next(&self) -> T {
-        if self.current() + 1 > self.source().len() {
-            self.source().null()
-        } else {
-            self.source().at(self.current() + 1)
-        }
+    fn peek_next(&self) -> char {
+        let current = self.current();
+        char_at(self.source(), current + self.peek().len_utf8())
     }
 
     fn is_at_end(&self) -> bool {
         self.current() >= self.source().len()
     }
 
-    fn advance(&mut self) -> T {
-        self.set_current(self.current() + 1);
-        *self.span() += (0, 1);
-        let ch = self.source().at(self.current() - 1);
-        if ch == self.source().nl() {
-            *self.span() += (1, 0);
-        }
+    fn advance(&mut self) -> char {
+        let ch = self.peek();
+        self.set_current(self.current() + ch.len_utf8());
+        *self.span() += ch;
         ch
     }
 }
 
 macro_rules! impl_generic_scanner {
     ( $f:expr ) => {
-        impl GenericScanner<char, Token> for Scanner {
-            fn source(&self) -> &impl Source<char> {
-                &self.source
+        impl<'a> GenericScanner<'a, Token<'a>> for Scanner<'a> {
+            fn source(&self) -> &'a str {
+                self.source
             }
 
-            fn tokens(&mut self) -> &mut Vec<Token> {
+            fn tokens(&mut self) -> &mut Vec<Token<'a>> {
                 &mut self.tokens
             }
 
@@ -11,14 +11,14 @@ pub enum FragmentType {
 
 #[derive(Debug, Clone)]
 #[doc(hidden)]
-pub struct Fragment {
+pub struct Fragment<'a> {
     pub r#type: FragmentType,
-    pub lexeme: String,
+    pub lexeme: &'a str,
     pub span: Span,
 }
 
-impl Fragment {
-    pub fn new(r#type: FragmentType, lexeme: String, span: Span) -> Self {
+impl<'a> Fragment<'a> {
+    pub fn new(r#type: FragmentType, lexeme: &'a str, span: Span) -> Self {
         Fragment {
             r#type,
             lexeme,
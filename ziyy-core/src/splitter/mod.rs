@@ -4,20 +4,21 @@ use fragment::Fragment;
 use fragment::FragmentType::{self, *};
 
 use crate::common::Span;
+use crate::scanner::char_at;
 use crate::{Error, ErrorType, Result};
 
 pub mod fragment;
 
 #[doc(hidden)]
-pub struct Splitter {
-    source: Vec<char>,
-    fragments: Vec<Fragment>,
+pub struct Splitter<'a> {
+    source: &'a str,
+    fragments: Vec<Fragment<'a>>,
     start: usize,
     current: usize,
     span: Span,
 }
 
-impl Default for Splitter {
+impl Default for Splitter<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -29,10 +30,10 @@ enum Quote {
     None,
 }
 
-impl Splitter {
+impl<'a> Splitter<'a> {
     pub fn new() -> Self {
         Self {
-            source: vec![],
+            source: "",
             fragments: vec![],
             start: 0,
             current: 0,
@@ -40,11 +41,15 @@ impl Splitter {
         }
     }
 
-    pub fn split(&mut self, source: String) -> Result<Vec<Fragment>> {
-        self.source = source.chars().collect();
+    pub fn split(&mut self, source: &'a str) -> Result<Vec<Fragment<'a>>> {
+        self.source = source;
+        self.fragments = Vec::with_capacity(source.len() / 4);
+        self.start = 0;
+        self.current = 0;
+        self.span = Span::default();
 
         macro_rules! consume_word {
-            ($c:ident) => {
+            () => {
                 loop {
                     if self.is_at_end() {
                         break;
@@ -58,11 +63,10 @@ impl Splitter {
                         break;
                     }
 
-                    if matches!($c, '\\') {
+                    // an escaped character never ends a word
+                    if self.advance() == '\\' {
                         self.advance();
                     }
-
-                    self.advance();
                 }
             };
         }
@@ -70,18 +74,18 @@ impl Splitter {
         while !self.is_at_end() {
             self.start = self.current;
 
-            let mut c = self.advance();
+            let c = self.advance();
 
             match c {
                 ' ' | '\r' | '\t' | '\n' => self.whitespace(),
                 '\\' => {
-                    c = self.advance();
-                    consume_word!(c);
+                    self.advance();
+                    consume_word!();
                     self.add_fragment(Word);
                 }
                 '<' => self.tag()?,
                 _ => {
-                    consume_word!(c);
+                    consume_word!();
                     self.add_fragment(Word);
                 }
             }
@@ -159,11 +163,7 @@ impl Splitter {
     }
 
     fn peek(&self) -> char {
-        if self.is_at_end() {
-            '\0'
-        } else {
-            self.source[self.current]
-        }
+        char_at(self.source, self.current)
     }
 
     fn is_at_end(&self) -> bool {
@@ -171,38 +171,22 @@ impl Splitter {
     }
 
     fn advance(&mut self) -> char {
-        self.current += 1;
-        self.span += (0, 1);
-        let ch = self.source[self.current - 1];
-        if ch == '\n' {
-            self.span += (1, 0);
+        if self.is_at_end() {
+            return '\0';
         }
+        let ch = self.peek();
+        self.current += ch.len_utf8();
+        self.span += ch;
         ch
     }
 
     fn add_fragment(&mut self, r#type: FragmentType) {
-        let text = self.source[self.start..self.current].to_string();
+        let text = &self.source[self.start..self.current];
         self.fragments.push(Fragment::new(r#type, text, self.span));
         self.span.tie_end();
     }
 }
 
-trait ToString {
-    fn to_string(&self) -> String;
-}
-
-impl ToString for [char] {
-    fn to_string(&self) -> String {
-        let mut text = String::with_capacity(self.len());
-
-        for ch in self {
-            text.push(*ch)
-        }
-
-        text
-    }
-}
-
 pub fn is_whitespace(c: char) -> bool {
     matches!(c, ' ' | '\t' | '\n' | '\x0c' | '\x0d')
 }
@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::mem::take;
+
+use crate::{RenderOptions, try_style_for};
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Text,
+    Escape,
+    Tag {
+        start: usize,
+        quote: Option<char>,
+        escaped: bool,
+    },
+}
+
+/// Renders markup read line by line, writing each top level element as soon
+/// as it is closed.
+///
+/// Only the element currently open is kept in memory, which makes it usable
+/// as a filter on endless input such as `tail -f`. An element still open
+/// after [`MAX_PENDING`](Streamer::MAX_PENDING) bytes is rendered as it is,
+/// and input which fails to parse is written as plain text. A `<` which does
+/// not start a tag, as in `x < y`, is text. Top level `<let />` bindings stay
+/// in effect for the rest of the stream, the oldest being dropped once they
+/// take more than `MAX_PENDING` bytes.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, Streamer};
+///
+/// let input = "<b>one</b>\n<i>two\nthree</i>\n";
+/// let mut out = vec![];
+/// Streamer::new(ColorLevel::TrueColor)
+///     .stream(input.as_bytes(), &mut out)
+///     .unwrap();
+///
+/// assert_eq!(out, b"\x1b[1mone\x1b[22m\n\x1b[3mtwo three\x1b[23m\n");
+/// ```
+pub struct Streamer {
+    options: RenderOptions,
+    prelude: VecDeque<String>,
+    pending: String,
+    scanned: usize,
+    state: State,
+    depth: usize,
+    has_content: bool,
+    has_bindings: bool,
+    at_line_start: bool,
+}
+
+impl Streamer {
+    /// Number of bytes of an open element kept before it is rendered anyway.
+    pub const MAX_PENDING: usize = 64 * 1024;
+
+    /// Creates a new Streamer that renders with `options`, such as the colors
+    /// of a [`ColorLevel`](crate::ColorLevel).
+    pub fn new(options: impl Into<RenderOptions>) -> Self {
+        Self {
+            options: options.into(),
+            prelude: VecDeque::new(),
+            pending: String::with_capacity(256),
+            scanned: 0,
+            state: State::Text,
+            depth: 0,
+            has_content: false,
+            has_bindings: false,
+            at_line_start: true,
+        }
+    }
+
+    /// Renders everything read from `reader` to `out`, flushing after each element.
+    pub fn stream(&mut self, mut reader: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+        let mut line = String::with_capacity(256);
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            self.feed(&line, out)?;
+        }
+        self.finish(out)
+    }
+
+    /// Feeds a piece of input, rendering the elements it completes.
+    ///
+    /// Elements are only rendered at the end of a line, so `text` should
+    /// usually be a whole line.
+    pub fn feed(&mut self, text: &str, out: &mut impl Write) -> io::Result<()> {
+        self.pending.push_str(text);
+        self.scan();
+
+        if self.depth == 0 && self.state == State::Text && self.pending.ends_with('\n') {
+            self.flush_pending(out)?;
+        } else if self.pending.len() > Self::MAX_PENDING {
+            self.finish(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders whatever input is left.
+    pub fn finish(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.state != State::Text || self.depth > 0 || self.scanned < self.pending.len() {
+            self.has_content = true;
+        }
+        self.state = State::Text;
+        self.depth = 0;
+        self.flush_pending(out)
+    }
+
+    /// Returns true if nothing was written yet or the output ends with a newline.
+    pub fn at_line_start(&self) -> bool {
+        self.at_line_start
+    }
+
+    fn scan(&mut self) {
+        let mut i = self.scanned;
+        while let Some(c) = self.pending[i..].chars().next() {
+            let next = i + c.len_utf8();
+            match self.state {
+                State::Text => match c {
+                    '\\' => self.state = State::Escape,
+                    '<' => match self.pending[next..].chars().next() {
+                        // wait for the rest of the line
+                        None => break,
+                        Some(c) if c.is_ascii_alphabetic() || matches!(c, '/' | '_' | '>') => {
+                            self.state = State::Tag {
+                                start: i,
+                                quote: None,
+                                escaped: false,
+                            }
+                        }
+                        Some(_) => self.has_content = true,
+                    },
+                    c if c.is_whitespace() => {}
+                    _ => self.has_content = true,
+                },
+                State::Escape => {
+                    self.has_content = true;
+                    self.state = State::Text;
+                }
+                State::Tag {
+                    start,
+                    quote: Some(q),
+                    escaped,
+                } => {
+                    self.state = State::Tag {
+                        start,
+                        quote: if c == q && !escaped { None } else { Some(q) },
+                        escaped: c == '\\' && !escaped,
+                    };
+                }
+                State::Tag {
+                    start, quote: None, ..
+                } => match c {
+                    '"' | '\'' => {
+                        self.state = State::Tag {
+                            start,
+                            quote: Some(c),
+                            escaped: false,
+                        }
+                    }
+                    '>' => {
+                        self.state = State::Text;
+                        let tag = &self.pending[start..next];
+                        if tag.starts_with("</") {
+                            self.depth = self.depth.saturating_sub(1);
+                            self.has_content = true;
+                        } else if tag.ends_with("/>") {
+                            if self.depth == 0 && tag_name(tag) == "let" {
+                                self.bind(tag.to_string());
+                                self.pending.replace_range(start..next, "");
+                                self.has_bindings = true;
+                                i = start;
+                                continue;
+                            }
+                            self.has_content = true;
+                        } else {
+                            self.depth += 1;
+                            self.has_content = true;
+                        }
+                    }
+                    _ => {}
+                },
+            }
+            i = next;
+        }
+        self.scanned = i;
+    }
+
+    /// Keeps the binding `tag` for the rest of the stream, dropping the
+    /// oldest bindings past [`MAX_PENDING`](Streamer::MAX_PENDING) bytes.
+    fn bind(&mut self, tag: String) {
+        self.prelude.push_back(tag);
+        let mut len: usize = self.prelude.iter().map(String::len).sum();
+        while len > Self::MAX_PENDING {
+            let Some(oldest) = self.prelude.pop_front() else {
+                break;
+            };
+            len -= oldest.len();
+        }
+    }
+
+    fn flush_pending(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let segment = take(&mut self.pending);
+        self.scanned = 0;
+
+        let newline = segment.ends_with('\n');
+        let mut buf = String::new();
+        if self.has_content {
+            let line = segment.trim_end_matches(['\n', '\r']);
+            let text = line.trim_start_matches([' ', '\t']);
+            // the renderer drops leading whitespace, so indentation is kept here
+            buf.push_str(&line[..line.len() - text.len()]);
+            let mut source: String = self.prelude.iter().map(String::as_str).collect();
+            source.push_str(text);
+            // a bad element is shown as it was written rather than ending the stream
+            match try_style_for(source, self.options) {
+                Ok(styled) => buf.push_str(&styled),
+                Err(_) => buf.push_str(text),
+            }
+            if newline {
+                buf.push('\n');
+            }
+        } else if !self.has_bindings {
+            // keep blank lines
+            buf.extend(segment.chars().filter(|c| *c == '\n'));
+        }
+
+        self.has_content = false;
+        self.has_bindings = false;
+        self.pending = segment;
+        self.pending.clear();
+
+        if !buf.is_empty() {
+            out.write_all(buf.as_bytes())?;
+            out.flush()?;
+            self.at_line_start = buf.ends_with('\n');
+        }
+
+        Ok(())
+    }
+}
+
+fn tag_name(tag: &str) -> &str {
+    let name = tag.trim_start_matches('<').trim_start();
+    let end = name
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+    &name[..end]
+}
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use crate::ColorLevel;
+
+/// Text styled once for every [`ColorLevel`], usually built at compile time
+/// by `ziyy_proc::style_set!`.
+///
+/// # Example
+///
+/// ```
+/// # use ziyy_core as ziyy;
+/// use ziyy::{ColorLevel, StyleSet};
+///
+/// const HELLO: StyleSet = StyleSet {
+///     truecolor: "\x1b[38;2;255;0;0mHello\x1b[39m",
+///     ansi256: "\x1b[38;5;196mHello\x1b[39m",
+///     ansi16: "\x1b[91mHello\x1b[39m",
+///     plain: "Hello",
+/// };
+///
+/// assert_eq!(HELLO.get(ColorLevel::None), "Hello");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StyleSet {
+    /// Text styled with 24-bit colors.
+    pub truecolor: &'static str,
+    /// Text styled with the 256 color palette.
+    pub ansi256: &'static str,
+    /// Text styled with the 16 standard colors.
+    pub ansi16: &'static str,
+    /// Text without any styles.
+    pub plain: &'static str,
+}
+
+impl StyleSet {
+    /// Returns the variant styled for `level`.
+    pub const fn get(&self, level: ColorLevel) -> &'static str {
+        match level {
+            ColorLevel::None => self.plain,
+            ColorLevel::Ansi16 => self.ansi16,
+            ColorLevel::Ansi256 => self.ansi256,
+            ColorLevel::TrueColor => self.truecolor,
+        }
+    }
+
+    /// Returns the variant styled for the color level of the current terminal.
+    pub fn as_str(&self) -> &'static str {
+        self.get(ColorLevel::current())
+    }
+}
+
+impl Display for StyleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    env,
+    io::{IsTerminal, stdout},
+    sync::LazyLock,
+};
+
+static COLOR_LEVEL: LazyLock<ColorLevel> = LazyLock::new(ColorLevel::detect);
+
+/// Amount of color a terminal is able to display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No styles at all.
+    None,
+    /// The 16 standard colors.
+    Ansi16,
+    /// The 256 color palette.
+    Ansi256,
+    /// 24-bit colors.
+    TrueColor,
+}
+
+impl ColorLevel {
+    /// Detects the color level of the terminal attached to stdout.
+    ///
+    /// `NO_COLOR` disables colors and `FORCE_COLOR` (`0` to `3`) overrides detection.
+    pub fn detect() -> Self {
+        if env::var_os("NO_COLOR").is_some_and(|s| !s.is_empty()) {
+            return ColorLevel::None;
+        }
+
+        if let Ok(force) = env::var("FORCE_COLOR") {
+            return match force.as_str() {
+                "0" | "false" => ColorLevel::None,
+                "2" => ColorLevel::Ansi256,
+                "3" => ColorLevel::TrueColor,
+                _ => ColorLevel::Ansi16,
+            };
+        }
+
+        if !stdout().is_terminal() {
+            return ColorLevel::None;
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+        if term == "dumb" {
+            return ColorLevel::None;
+        }
+
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if matches!(colorterm.as_str(), "truecolor" | "24bit")
+            || env::var_os("WT_SESSION").is_some()
+        {
+            ColorLevel::TrueColor
+        } else if term.contains("256color") {
+            ColorLevel::Ansi256
+        } else {
+            ColorLevel::Ansi16
+        }
+    }
+
+    /// Returns the color level detected on first use.
+    pub fn current() -> Self {
+        *COLOR_LEVEL
+    }
+}
+
+/// How a terminal is able to show images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Graphics {
+    /// The kitty graphics protocol.
+    Kitty,
+    /// The inline images protocol of iTerm2, which WezTerm supports too.
+    Iterm2,
+    /// Sixel graphics.
+    Sixel,
+    /// Half block characters colored with the pixels of the image.
+    Blocks,
+    /// The alternative text of the image.
+    Alt,
+}
+
+impl Graphics {
+    /// Detects how the terminal attached to stdout is able to show images,
+    /// falling back to blocks when it has 256 colors or more.
+    pub fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if !stdout().is_terminal() {
+            // graphics protocols are only written to terminals
+        } else if env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || program == "ghostty"
+        {
+            return Graphics::Kitty;
+        } else if matches!(program.as_str(), "iTerm.app" | "WezTerm")
+            || env::var("LC_TERMINAL").is_ok_and(|s| s == "iTerm2")
+        {
+            return Graphics::Iterm2;
+        } else if ["sixel", "foot", "mlterm"].iter().any(|s| term.contains(s)) {
+            return Graphics::Sixel;
+        }
+
+        match ColorLevel::current() >= ColorLevel::Ansi256 {
+            true => Graphics::Blocks,
+            false => Graphics::Alt,
+        }
+    }
+}
+
+/// How `:name:` shortcodes in text are expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Icons {
+    /// Shortcodes are left as they are.
+    None,
+    /// Emoji and Nerd Font icons.
+    Unicode,
+    /// ASCII such as `[ok]` and `[x]`, for terminals and locales without
+    /// the glyphs.
+    Ascii,
+}
+
+impl Icons {
+    /// Detects whether the terminal attached to stdout is able to show
+    /// icons, which takes a UTF-8 locale, or Windows Terminal on Windows.
+    pub fn detect() -> Self {
+        if env::var("TERM").is_ok_and(|term| term == "dumb") {
+            return Icons::Ascii;
+        }
+        if cfg!(windows) {
+            return match env::var_os("WT_SESSION").is_some() {
+                true => Icons::Unicode,
+                false => Icons::Ascii,
+            };
+        }
+
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(|name| env::var(name).ok().filter(|s| !s.is_empty()));
+        match locale {
+            Some(locale) => {
+                let locale = locale.to_ascii_lowercase();
+                match locale.contains("utf-8") || locale.contains("utf8") {
+                    true => Icons::Unicode,
+                    false => Icons::Ascii,
+                }
+            }
+            None => Icons::Unicode,
+        }
+    }
+}
+
+/// Returns the width in columns of the terminal, as given by the `COLUMNS`
+/// environment variable.
+///
+/// Shells set `COLUMNS` without exporting it, so programs which need the
+/// width should get it exported or take an explicit width, such as the one
+/// of [`RenderOptions`](crate::RenderOptions).
+pub fn terminal_width() -> Option<usize> {
+    env::var("COLUMNS")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&columns| columns > 0)
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::style_at;
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_pads_spans_to_their_width() {
+    let styled = try_style("[<span width='6' align='right'>42</span>]");
+    assert_eq!(styled.unwrap(), "[    42]");
+
+    let styled = try_style("[<span min-width='5' align='center'>✅</span>]");
+    assert_eq!(styled.unwrap(), "[ ✅  ]");
+}
+
+#[test]
+pub fn it_truncates_with_an_ellipsis() {
+    let styled = try_style("<span max-width='6'>abcdefghij</span>");
+    assert_eq!(styled.unwrap(), "abcde…");
+
+    let styled = try_style("<span width='7' truncate='middle'>abcdefghij</span>");
+    assert_eq!(styled.unwrap(), "abc…hij");
+
+    let styled = try_style("<span width='5' truncate='start'><b>abcdefghij</b></span>");
+    assert_eq!(styled.unwrap(), "\x1b[1m…ghij\x1b[22m");
+
+    let styled = try_style("<p width='10' truncate='end'>the quick brown fox</p>");
+    assert_eq!(styled.unwrap(), "the quick…");
+}
+
+#[test]
+pub fn it_aligns_blocks() {
+    let styled = style_at("<p align='right'>a<br/>bbb</p>", 6);
+    assert_eq!(styled, "     a\n   bbb");
+
+    let styled = try_style("<p align='center' width='12'>the quick brown fox</p>");
+    assert_eq!(styled.unwrap(), " the quick  \n brown fox  ");
+}
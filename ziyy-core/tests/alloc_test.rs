@@ -0,0 +1,85 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ziyy_core::{Indexer, Span, Splitter};
+
+struct Counter;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+pub fn it_splits_without_copying_lexemes() {
+    let source = "<b>word</b> and <i c='red'>more words</i> ".repeat(200);
+
+    let mut splitter = Splitter::new();
+    let mut count = 0;
+    let n = allocations(|| {
+        let frags = splitter.split(&source).unwrap();
+        count = frags.len();
+    });
+
+    assert_eq!(count, 2400);
+    // only the growth of the fragment list allocates
+    assert!(n <= 16, "{n} allocations");
+}
+
+#[test]
+pub fn it_borrows_source_without_placeholders() {
+    let mut indexer = Indexer::new();
+    let n = allocations(|| {
+        let source = indexer.index("<b>no placeholders</b>");
+        assert_eq!(source, "<b>no placeholders</b>");
+    });
+    assert_eq!(n, 0);
+}
+
+#[test]
+pub fn it_keeps_byte_offsets_in_spans() {
+    let source = "<b>héllo</b>\n wörld";
+    let frags = Splitter::new().split(source).unwrap();
+
+    for frag in &frags {
+        assert_eq!(&source[frag.span.range()], frag.lexeme);
+    }
+    let last = frags.last().unwrap();
+    assert_eq!(
+        last.span,
+        Span::new(
+            ziyy_core::Position::locate(source, 15),
+            ziyy_core::Position::locate(source, source.len()),
+        )
+    );
+    assert_eq!(last.span.start().line, 2);
+    assert_eq!(last.span.start().column, 2);
+}
+
+#[test]
+pub fn it_keeps_byte_offsets_in_color_spans() {
+    let source = "é <c rgb='1,f,3'>";
+    let err = ziyy_core::try_style(source).unwrap_err();
+
+    assert_eq!(&source[err.span.range()], "f");
+    assert_eq!(err.span.start().column, 13);
+}
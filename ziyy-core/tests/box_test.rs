@@ -0,0 +1,31 @@
+mod common;
+
+use common::style_at;
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_draws_a_border_with_a_title() {
+    let styled = try_style("<box border='rounded' title='Warning'>disk is full</box>");
+    assert_eq!(
+        styled.unwrap(),
+        "╭─ Warning ────╮\n│ disk is full │\n╰──────────────╯"
+    );
+}
+
+#[test]
+pub fn it_wraps_and_pads_content() {
+    let styled = try_style("<box border='ascii' padding='1 2' width='14'>one two three</box>");
+    assert_eq!(
+        styled.unwrap(),
+        "+------------+\n|            |\n|  one two   |\n|  three     |\n|            |\n+------------+"
+    );
+
+    let styled = style_at("<box border='none'>the quick brown fox</box>", 12);
+    assert_eq!(styled, " the quick  \n brown fox  ");
+}
+
+#[test]
+pub fn it_fills_the_background_of_every_line() {
+    let styled = try_style("<box x='blue' border='none' padding='0'>a<br/>bbb</box>");
+    assert_eq!(styled.unwrap(), "\x1b[44ma  \x1b[49m\n\x1b[44mbbb\x1b[49m");
+}
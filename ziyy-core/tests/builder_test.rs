@@ -0,0 +1,30 @@
+use ziyy_core::{Color, Doc, try_style};
+
+#[test]
+pub fn it_builds_the_same_output_as_markup() {
+    let out = Doc::new()
+        .div(|div| {
+            div.p(|p| p.bold("Usage:").text(" tool [OPTIONS]"))
+                .br()
+                .p(|p| {
+                    p.fg(Color::fg_rgb(255, 0, 0), "red")
+                        .text(" and ")
+                        .tag("i", |i| i.underline("both"))
+                })
+        })
+        .render()
+        .unwrap();
+
+    let expected = try_style(
+        "<div><p><b>Usage:</b> tool [OPTIONS]</p><br />\
+         <p><c rgb='255,0,0'>red</c> and <i><u>both</u></i></p></div>",
+    )
+    .unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+pub fn it_never_interprets_text() {
+    let out = Doc::new().bold(r"<b>\n</b>").render().unwrap();
+    assert_eq!(out, "\x1b[1m<b>\\n</b>\x1b[22m");
+}
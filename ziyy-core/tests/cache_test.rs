@@ -0,0 +1,60 @@
+use ziyy_core::{Cache, ColorLevel, try_style};
+
+#[test]
+pub fn it_caches_only_when_asked() {
+    let source = "<let id='w' c='red' /><p><w>a</w> <b>b</b></p>";
+    let first = try_style(source).unwrap();
+    let second = try_style(source).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(Cache::with_thread_local(|cache| cache.len()), 0);
+
+    let cached = Cache::with_thread_local(|cache| cache.render(source, ColorLevel::TrueColor));
+    assert_eq!(cached.unwrap(), first);
+    assert_eq!(Cache::with_thread_local(|cache| cache.len()), 1);
+}
+
+#[test]
+pub fn it_caches_per_color_level() {
+    let mut cache = Cache::new(4);
+    let source = "<c rgb='255,0,0'>red</c>";
+
+    let mut buf = String::new();
+    cache
+        .resolve(source, ColorLevel::TrueColor)
+        .unwrap()
+        .root()
+        .to_string(&mut buf);
+    assert_eq!(buf, "\x1b[38;2;255;0;0mred\x1b[39m");
+
+    buf.clear();
+    cache
+        .resolve(source, ColorLevel::Ansi256)
+        .unwrap()
+        .root()
+        .to_string(&mut buf);
+    assert_eq!(buf, "\x1b[38;5;196mred\x1b[39m");
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+pub fn it_drops_the_oldest_when_full() {
+    let mut cache = Cache::new(2);
+    for source in ["a", "b", "c"] {
+        cache.resolve(source, ColorLevel::TrueColor).unwrap();
+    }
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+pub fn it_hands_out_copies() {
+    let mut cache = Cache::new(2);
+    let source = "<b>a</b> <i>b</i>";
+    let doc = cache.resolve(source, ColorLevel::TrueColor).unwrap();
+    for child in doc.root().children().collect::<Vec<_>>() {
+        child.remove().unwrap();
+    }
+
+    let rendered = cache.render(source, ColorLevel::TrueColor).unwrap();
+    assert_eq!(rendered, "\x1b[1ma\x1b[22m \x1b[3mb\x1b[23m");
+    assert_eq!(cache.len(), 1);
+}
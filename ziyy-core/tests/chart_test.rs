@@ -0,0 +1,60 @@
+use ziyy_core::{ColorLevel, ErrorType, Live, try_style};
+
+#[test]
+pub fn it_draws_sparklines() {
+    let styled = try_style("<sparkline data='0 1 2 3 4 5 6 7'/>");
+    assert_eq!(styled.unwrap(), "▁▂▃▄▅▆▇█");
+
+    let styled = try_style("<sparkline data='[9, 0, 5, 10]' width='3' min='0' max='20'/>|");
+    assert_eq!(styled.unwrap(), "▁▃▅|");
+
+    let styled = try_style("<sparkline data='2, 2'/><sparkline data='{0}'/>|");
+    assert_eq!(styled.unwrap(), "▄▄|");
+}
+
+#[test]
+pub fn it_draws_bars() {
+    let styled = try_style("<bar value='42' width='10'/>|");
+    assert_eq!(styled.unwrap(), "████▎     |");
+
+    let styled = try_style("<bar value='7' max='4' width='2'/>|<bar value='0' width='2'/>|");
+    assert_eq!(styled.unwrap(), "██|  |");
+}
+
+#[test]
+pub fn it_colors_by_thresholds() {
+    let styled = try_style("<sparkline data='1 6 9 2' thresholds='9:red; 5:yellow'/>");
+    assert_eq!(styled.unwrap(), "▁\x1b[33m▅\x1b[31m█\x1b[39m▂");
+
+    let styled = try_style("<bar value='95' width='2' thresholds='80:#ff0000'/>");
+    assert_eq!(styled.unwrap(), "\x1b[38;2;255;0;0m█▉\x1b[39m");
+}
+
+#[test]
+pub fn it_errors_on_malformed_thresholds() {
+    let error = |thresholds: &str| {
+        try_style(format!("<bar value='5' thresholds='{thresholds}'/>"))
+            .unwrap_err()
+            .r#type
+    };
+    assert!(matches!(
+        error("3:green,6:red"),
+        ErrorType::InvalidTagAttributeValue
+    ));
+    assert!(matches!(error("3:gold"), ErrorType::InvalidColor));
+    assert!(matches!(
+        error("3 green"),
+        ErrorType::InvalidTagAttributeValue
+    ));
+    assert!(matches!(error("x:red"), ErrorType::InvalidNumber));
+
+    let styled = try_style("<bar value='5' max='10' width='1' thresholds='3:green; 6:red;'/>");
+    assert_eq!(styled.unwrap(), "\x1b[32m▌\x1b[39m");
+}
+
+#[test]
+pub fn it_renders_live_charts() {
+    let mut live = Live::new("<sparkline data='{}'/>", ColorLevel::None);
+    assert_eq!(live.render(&[&"1 2"]).unwrap(), "▁█");
+    assert_eq!(live.render(&[&"1 2 3"]).unwrap(), "\r\x1b[2K\r▁▅█");
+}
@@ -0,0 +1,52 @@
+use ziyy_core::{ColorLevel, try_style, try_style_for};
+
+#[test]
+pub fn it_highlights_rust() {
+    let styled = try_style("<code lang='rust'>\nfn main() { let s = \"x\"; } // done\n</code>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[35mfn\x1b[39m main() { \x1b[35mlet\x1b[39m s = \x1b[32m\"x\"\x1b[39m; } \x1b[2;3m// done\x1b[22;23m"
+    );
+}
+
+#[test]
+pub fn it_highlights_config_and_diffs() {
+    let styled = try_style("<code lang='json'>{\"a\": 1, \"b\": null}</code>");
+    assert_eq!(
+        styled.unwrap(),
+        "{\x1b[34m\"a\"\x1b[39m: \x1b[36m1\x1b[39m, \x1b[34m\"b\"\x1b[39m: \x1b[36mnull\x1b[39m}"
+    );
+
+    let styled = try_style("<code lang='toml'>[a]\nb = 'c' # d</code>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[1;34m[a]\x1b[22;39m\n\x1b[34mb\x1b[39m = \x1b[32m'c'\x1b[39m \x1b[2;3m# d\x1b[22;23m"
+    );
+
+    let styled = try_style("<code lang='diff'>-a\n+b</code>");
+    assert_eq!(styled.unwrap(), "\x1b[31m-a\x1b[39m\n\x1b[32m+b\x1b[39m");
+}
+
+#[test]
+pub fn it_takes_styles_from_bindings() {
+    let styled = try_style("<let id='code.variable' c='red' u/><code lang='sh'>echo $HOME</code>");
+    assert_eq!(styled.unwrap(), "echo \x1b[4;31m$HOME\x1b[24;39m");
+
+    let styled = try_style("<code lang='unknown'>fn  x</code>");
+    assert_eq!(styled.unwrap(), "fn  x");
+}
+
+#[test]
+pub fn it_survives_unterminated_input() {
+    let inputs = [
+        "x'", "'x", "\"x", "${HOME", "echo ${", "$", "/* x", "// x", "# x", "#[x", "r#\"x", "b\"x",
+        "'''x", "\"\"\"x", "[x", "a = -", "1.",
+    ];
+    for lang in ["rust", "json", "toml", "sh", "diff"] {
+        for input in inputs {
+            let source = format!("<code lang='{lang}'>{input}</code>");
+            let styled = try_style_for(&source, ColorLevel::None);
+            assert_eq!(styled.unwrap(), input, "{lang}: {input}");
+        }
+    }
+}
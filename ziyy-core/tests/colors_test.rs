@@ -6,10 +6,9 @@ use ziyy_core::{Document, Indexer, Parser, Resolver, Result, Splitter};
 
 fn try_style<T: AsRef<str>>(source: T) -> Result<Rc<Document>> {
     let mut indexer = Indexer::new();
-    let source = indexer.index(source.as_ref().to_string());
+    let source = indexer.index(source.as_ref());
     let mut splitter = Splitter::new();
-    #[allow(clippy::unnecessary_to_owned)]
-    let frags = splitter.split(source)?;
+    let frags = splitter.split(&source)?;
 
     let parser = Parser::new(false);
     let chunks = parser.parse(frags)?;
@@ -21,7 +20,7 @@ fn try_style<T: AsRef<str>>(source: T) -> Result<Rc<Document>> {
 fn assert_fg_colors_eq(source: &str, color: Color) {
     let styled = try_style(source);
     let _ = styled.is_ok_and(|doc| {
-        let node = doc.get(1);
+        let node = doc.select("*").unwrap().unwrap();
         let chunk = node.chunk().borrow();
         let other = chunk.tag().unwrap().fg_color();
         assert!(other.eq(&color), "expected {color} and got {other}");
@@ -32,7 +31,7 @@ fn assert_fg_colors_eq(source: &str, color: Color) {
 fn assert_bg_colors_eq(source: &str, color: Color) {
     let styled = try_style(source);
     let _ = styled.is_ok_and(|doc| {
-        let node = doc.get(1);
+        let node = doc.select("*").unwrap().unwrap();
         let chunk = node.chunk().borrow();
         let other = chunk.tag().unwrap().bg_color();
         assert!(other.eq(&color), "expected {color} and got {other}");
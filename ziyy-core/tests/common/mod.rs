@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+use ziyy_core::{RenderOptions, try_style_for};
+
+/// Styles `source` laid out in `width` columns.
+pub fn style_at(source: &str, width: usize) -> String {
+    let options = RenderOptions {
+        width: Some(width),
+        ..RenderOptions::default()
+    };
+    try_style_for(source, options).unwrap()
+}
+
+/// Removes the escape sequences of `s`.
+pub fn strip(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
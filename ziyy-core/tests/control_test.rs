@@ -0,0 +1,44 @@
+use ziyy_core::{ColorLevel, RenderOptions, try_style, try_style_for};
+
+#[test]
+pub fn it_moves_the_cursor() {
+    let styled = try_style("a<cursor up='2' col='4' save/>b<cursor restore visible='false'/>");
+    assert_eq!(styled.unwrap(), "a\x1b[2A\x1b[4G\x1b7b\x1b8\x1b[?25l");
+
+    let styled = try_style("<cursor to='3,4'/><erase line='end' screen/>");
+    assert_eq!(styled.unwrap(), "\x1b[3;4H\x1b[0K\x1b[2J");
+}
+
+#[test]
+pub fn it_sets_the_title_and_screen() {
+    let styled = try_style("<screen alt/><title>Build <b>3</b>/5</title><bell/><screen main/>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[?1049h\x1b]2;Build 3/5\x07\x07\x1b[?1049l"
+    );
+
+    let styled = try_style("<title icon>a\\eb</title>");
+    assert_eq!(styled.unwrap(), "\x1b]0;ab\x07");
+}
+
+#[test]
+pub fn it_strips_control_sequences() {
+    let styled = try_style_for(
+        "<screen alt/>a<cursor up/><erase line/><b>b</b><bell/>",
+        ColorLevel::None,
+    );
+    assert_eq!(styled.unwrap(), "ab");
+}
+
+#[test]
+pub fn it_writes_no_control_sequences_off_terminals() {
+    let options = RenderOptions {
+        controls: false,
+        ..RenderOptions::default()
+    };
+    let styled = try_style_for(
+        "<screen alt/>a<cursor up='2'/><title>t</title><b>b</b><bell/>",
+        options,
+    );
+    assert_eq!(styled.unwrap(), "a\x1b[1mb\x1b[22m");
+}
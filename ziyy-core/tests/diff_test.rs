@@ -0,0 +1,28 @@
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_styles_diff_lines() {
+    let styled = try_style(
+        "<diff>\ndiff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n</diff>",
+    );
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[1mdiff --git a/x b/x\x1b[22m\n\x1b[1m--- a/x\x1b[22m\n\x1b[1m+++ b/x\x1b[22m\n\x1b[36m@@ -1,2 +1,2 @@\x1b[39m\n a\n\x1b[31m-b\x1b[39m\n\x1b[32m+c\x1b[39m"
+    );
+}
+
+#[test]
+pub fn it_highlights_changed_words() {
+    let styled = try_style("<diff words>-let a = 1;\n+let b = 1;</diff>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[31m-let \x1b[7ma\x1b[27m = 1;\x1b[39m\n\x1b[32m+let \x1b[7mb\x1b[27m = 1;\x1b[39m"
+    );
+}
+
+#[test]
+pub fn it_takes_styles_from_bindings() {
+    let styled =
+        try_style("<let id='diff.added' c='blue'/><let id='diff.context' d/><diff>+a\n b</diff>");
+    assert_eq!(styled.unwrap(), "\x1b[34m+a\x1b[39m\n\x1b[2m b\x1b[22m");
+}
@@ -0,0 +1,55 @@
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+use ziyy_core::{ColorLevel, Edge, FrozenDocument, resolve, try_style};
+
+const SOURCE: &str = "<let id='w' c='red' /><p>a <w>b</w> <b>c <i>d</i></b></p>";
+
+fn frozen() -> &'static FrozenDocument {
+    static DOC: OnceLock<FrozenDocument> = OnceLock::new();
+    DOC.get_or_init(|| resolve(SOURCE, ColorLevel::TrueColor).unwrap().freeze())
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+pub fn it_is_send_and_sync() {
+    assert_send_sync::<FrozenDocument>();
+    assert_send_sync::<Arc<FrozenDocument>>();
+}
+
+#[test]
+pub fn it_renders_from_many_threads() {
+    let expected = try_style(SOURCE).unwrap();
+    let handles: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| frozen().render()))
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
+
+#[test]
+pub fn it_keeps_the_traversal_api() {
+    let doc = resolve(SOURCE, ColorLevel::TrueColor).unwrap();
+    let frozen = doc.freeze();
+
+    let ids: Vec<_> = doc.root().descendants().map(|node| node.id()).collect();
+    let frozen_ids: Vec<_> = frozen.root().descendants().map(|node| node.id()).collect();
+    assert_eq!(ids, frozen_ids);
+
+    let edges = doc.root().traverse().count();
+    let frozen_edges = frozen.root().traverse().count();
+    assert_eq!(edges, frozen_edges);
+
+    let last = frozen.root().descendants().last().unwrap();
+    assert_eq!(last.ancestors().last(), Some(frozen.root()));
+    assert!(
+        matches!(frozen.root().traverse().last(), Some(Edge::Close(node)) if node == frozen.root())
+    );
+    assert_eq!(
+        frozen.root().children().count(),
+        doc.root().children().count()
+    );
+    assert_eq!(format!("{frozen}"), format!("{doc}"));
+}
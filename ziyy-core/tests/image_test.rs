@@ -0,0 +1,124 @@
+use std::{env, fs, path::PathBuf};
+
+use ziyy_core::{Graphics, Parser, Resolver, Splitter, try_style};
+
+fn render(source: &str, graphics: Graphics) -> String {
+    let frags = Splitter::new().split(source).unwrap();
+    let chunks = Parser::new(false).parse(frags).unwrap();
+    let mut resolver = Resolver::new(false);
+    resolver.set_graphics(graphics);
+    let mut buf = String::new();
+    resolver.resolve(chunks).unwrap().root().to_string(&mut buf);
+    buf
+}
+
+fn write(name: &str, data: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("ziyy-{}-{name}", std::process::id()));
+    fs::write(&path, data).unwrap();
+    path
+}
+
+/// A PNG of one green pixel, stored without compression.
+fn png() -> Vec<u8> {
+    sized_png(1, 1, 8)
+}
+
+/// A PNG with the header of a `width` by `height` image of `depth` bits per
+/// channel, holding one green pixel.
+fn sized_png(width: u32, height: u32, depth: u8) -> Vec<u8> {
+    let chunk = |kind: &[u8], body: &[u8]| {
+        let mut chunk = (body.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(body);
+        chunk.extend_from_slice(&[0; 4]);
+        chunk
+    };
+    let raw = [0, 0, 255, 0, 255];
+    let mut zlib = vec![0x78, 0x01, 1, 5, 0, !5, !0];
+    zlib.extend_from_slice(&raw);
+    zlib.extend_from_slice(&[0; 4]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    let mut header = [width.to_be_bytes(), height.to_be_bytes()].concat();
+    header.extend_from_slice(&[depth, 6, 0, 0, 0]);
+    png.extend(chunk(b"IHDR", &header));
+    png.extend(chunk(b"IDAT", &zlib));
+    png.extend(chunk(b"IEND", &[]));
+    png
+}
+
+#[test]
+pub fn it_draws_half_blocks() {
+    // red above blue, then white above black
+    let ppm = b"P6\n2 2\n255\n\xff\x00\x00\xff\xff\xff\x00\x00\xff\x00\x00\x00";
+    let path = write("a.ppm", ppm);
+    let styled = try_style(format!("<img src='{}'/>", path.display()));
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[38;2;255;0;0;48;2;0;0;255m▀\x1b[38;2;255;255;255;48;2;0;0;0m▀\x1b[39;49m"
+    );
+
+    let styled = try_style(format!("<img src='{}' width='1'/>", path.display()));
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[38;2;255;127;127;48;2;0;0;127m▀\x1b[39;49m"
+    );
+}
+
+#[test]
+pub fn it_writes_graphics_protocols() {
+    let path = write("a.png", &png());
+    let source = format!("<img src='{}'/>", path.display());
+
+    let kitty = render(&source, Graphics::Kitty);
+    assert!(kitty.starts_with("\x1b_Ga=T,f=100,c=1,r=1,m=0;iVBORw0KGgo"));
+    assert!(kitty.ends_with("\x1b\\"));
+
+    let iterm2 = render(&source, Graphics::Iterm2);
+    assert!(iterm2.starts_with("\x1b]1337;File=inline=1;size=73;width=1;height=1;"));
+    assert!(iterm2.ends_with("\x07"));
+
+    let sixel = render(&source, Graphics::Sixel);
+    assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;10;20#0;2;0;0;0"));
+    assert!(sixel.ends_with("#30!10~-#30!10~-#30!10~-#30!10B-\x1b\\"));
+
+    let blocks = render(&source, Graphics::Blocks);
+    assert_eq!(blocks, "\x1b[38;2;0;255;0;48;2;0;255;0m▀\x1b[39;49m");
+}
+
+#[test]
+pub fn it_falls_back_to_alt_text() {
+    let styled = try_style("<img src='missing.png' alt='Logo'/> v1");
+    assert_eq!(styled.unwrap(), "Logo v1");
+
+    let path = write("b.png", &png());
+    let source = format!("<img src='{}' alt='Logo'/>", path.display());
+    assert_eq!(render(&source, Graphics::Alt), "Logo");
+
+    let source = format!(
+        "<img src='{}' alt='Logo' protocol='kitty'/>",
+        path.display()
+    );
+    assert_eq!(render(&source, Graphics::Alt), "Logo");
+}
+
+#[test]
+pub fn it_only_writes_decoded_images() {
+    let path = write("secret.txt", b"not an image");
+    let source = format!("<img src='{}' width='1' alt='x'/>", path.display());
+    for graphics in [Graphics::Kitty, Graphics::Iterm2, Graphics::Sixel] {
+        assert_eq!(render(&source, graphics), "x");
+    }
+
+    // a valid PNG followed by more than the largest file read
+    let mut data = png();
+    data.resize(16 << 20, 0);
+    let path = write("d.png", &data);
+    let source = format!("<img src='{}' alt='Big'/>", path.display());
+    assert!(render(&source, Graphics::Kitty).starts_with("\x1b_G"));
+
+    data.push(0);
+    let path = write("big.png", &data);
+    let source = format!("<img src='{}' alt='Big'/>", path.display());
+    assert_eq!(render(&source, Graphics::Kitty), "Big");
+}
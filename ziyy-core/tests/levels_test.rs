@@ -0,0 +1,50 @@
+use ziyy_core::{Color, ColorLevel, try_style_for};
+
+#[test]
+pub fn it_keeps_colors_at_truecolor() {
+    let styled = try_style_for("<c rgb='150, 75, 0'>x</c>", ColorLevel::TrueColor);
+    assert_eq!(styled.unwrap(), "\x1b[38;2;150;75;0mx\x1b[39m");
+}
+
+#[test]
+pub fn it_downgrades_rgb_to_ansi256() {
+    let styled = try_style_for("<c rgb='255, 0, 0'>x</c>", ColorLevel::Ansi256);
+    assert_eq!(styled.unwrap(), "\x1b[38;5;196mx\x1b[39m");
+
+    let styled = try_style_for("<x rgb='128, 128, 128'>x</x>", ColorLevel::Ansi256);
+    assert_eq!(styled.unwrap(), "\x1b[48;5;243mx\x1b[49m");
+}
+
+#[test]
+pub fn it_downgrades_to_ansi16() {
+    let styled = try_style_for("<c rgb='255, 0, 0'>x</c>", ColorLevel::Ansi16);
+    assert_eq!(styled.unwrap(), "\x1b[91mx\x1b[39m");
+
+    let styled = try_style_for("<c c='byte(34)'>x</c>", ColorLevel::Ansi16);
+    assert_eq!(styled.unwrap(), "\x1b[32mx\x1b[39m");
+
+    let styled = try_style_for("<c red>x</c>", ColorLevel::Ansi16);
+    assert_eq!(styled.unwrap(), "\x1b[31mx\x1b[39m");
+}
+
+#[test]
+pub fn it_strips_styles_without_colors() {
+    let styled = try_style_for("<b c='#ff0000'>x</b> y", ColorLevel::None);
+    assert_eq!(styled.unwrap(), "x y");
+}
+
+#[test]
+pub fn it_downgrades_colors() {
+    assert_eq!(
+        Color::fg_rgb(0, 0, 0).downgrade(ColorLevel::Ansi256),
+        Color::fg_fixed(16)
+    );
+    assert_eq!(
+        Color::bg_rgb(255, 255, 255).downgrade(ColorLevel::Ansi16),
+        Color::four_bit(107)
+    );
+    assert_eq!(
+        Color::bg_fixed(21).downgrade(ColorLevel::TrueColor),
+        Color::bg_fixed(21)
+    );
+}
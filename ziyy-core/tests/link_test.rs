@@ -0,0 +1,90 @@
+use ziyy_core::{
+    ColorLevel, Icons, Parser, RenderOptions, Resolver, Splitter, file_url, try_style,
+    try_style_for,
+};
+
+fn unlinked(source: &str) -> String {
+    let frags = Splitter::new().split(source).unwrap();
+    let chunks = Parser::new(false).parse(frags).unwrap();
+    let mut resolver = Resolver::new(false);
+    resolver.set_hyperlinks(false);
+    let mut buf = String::new();
+    resolver.resolve(chunks).unwrap().root().to_string(&mut buf);
+    buf
+}
+
+#[test]
+pub fn it_writes_hyperlinks() {
+    let styled = try_style("<a href='https://a.b/c d' id='x:1'><b>docs</b></a>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b]8;id=x1;https://a.b/c%20d\x1b\\\x1b[1mdocs\x1b[22m\x1b]8;;\x1b\\"
+    );
+
+    let styled = try_style("<a href='https://a.b/\x1b\\x'>x</a>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b]8;;https://a.b/%1B\\x\x1b\\x\x1b]8;;\x1b\\"
+    );
+}
+
+#[test]
+pub fn it_falls_back_to_text() {
+    assert_eq!(
+        unlinked("see <a href='https://a.b'><i>docs</i></a>."),
+        "see \x1b[3mdocs\x1b[23m (https://a.b)."
+    );
+    assert_eq!(
+        unlinked("<a href='https://a.b'>https://a.b</a>"),
+        "https://a.b"
+    );
+}
+
+#[test]
+pub fn it_writes_invalid_targets_as_text() {
+    let styled = try_style("<a href='javascript:alert(1)'>x</a>");
+    assert_eq!(styled.unwrap(), "x (javascript:alert(1))");
+
+    let styled = try_style("<a href=''>x</a> <a href='nowhere'>y</a> <a href='http://'>z</a>");
+    assert_eq!(styled.unwrap(), "x y (nowhere) z (http://)");
+
+    let styled = try_style("<a href='mailto:a@b.c'>m</a><a href='file:///tmp'>f</a>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b]8;;mailto:a@b.c\x1b\\m\x1b]8;;\x1b\\\x1b]8;;file:///tmp\x1b\\f\x1b]8;;\x1b\\"
+    );
+}
+
+#[test]
+pub fn it_links_files() {
+    let url = file_url("/tmp/a b").unwrap();
+    assert!(url.starts_with("file://"));
+    assert!(url.ends_with("/tmp/a%20b"));
+
+    let styled = try_style("<a file='/tmp/a b'>a</a>");
+    assert_eq!(
+        styled.unwrap(),
+        format!("\x1b]8;;{url}\x1b\\a\x1b]8;;\x1b\\")
+    );
+}
+
+#[test]
+pub fn it_parses_link_text() {
+    let options = RenderOptions {
+        icons: Icons::Unicode,
+        ..RenderOptions::default()
+    };
+    let styled = try_style_for("<a href='https://a.b'>:rocket: \\<1\\></a>", options);
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b]8;;https://a.b\x1b\\🚀 <1>\x1b]8;;\x1b\\"
+    );
+
+    let options = RenderOptions {
+        level: ColorLevel::None,
+        hyperlinks: false,
+        ..RenderOptions::default()
+    };
+    let styled = try_style_for("<a href='https://a.b'><b>docs</b></a>", options);
+    assert_eq!(styled.unwrap(), "docs (https://a.b)");
+}
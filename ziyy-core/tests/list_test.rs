@@ -0,0 +1,46 @@
+mod common;
+
+use common::style_at;
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_bullets_unordered_lists() {
+    let styled = try_style("<ul>\n  <li>one</li>\n  <li>two</li>\n</ul>");
+    assert_eq!(styled.unwrap(), "• one\n• two");
+}
+
+#[test]
+pub fn it_indents_nested_lists() {
+    let styled = try_style("<ul><li>a<ul><li>b<ul><li>c</li></ul></li></ul></li><li>d</li></ul>");
+    assert_eq!(styled.unwrap(), "• a\n  ◦ b\n    ▪ c\n• d");
+}
+
+#[test]
+pub fn it_right_aligns_ordered_markers() {
+    let styled = try_style("<ol start='9'><li>nine</li><li>ten</li></ol>");
+    assert_eq!(styled.unwrap(), " 9. nine\n10. ten");
+
+    let styled = try_style("<ol marker='(i)' indent='2'><li>x</li><li>y</li><li>z</li></ol>");
+    assert_eq!(styled.unwrap(), "    (i) x\n   (ii) y\n  (iii) z");
+
+    let styled = try_style("<ol marker='A)'><li>x</li><li>y</li></ol>");
+    assert_eq!(styled.unwrap(), "A) x\nB) y");
+}
+
+#[test]
+pub fn it_counts_the_last_counter_in_markers() {
+    let styled = try_style("<ol marker='Part 1.' start='2'><li>x</li><li>y</li></ol>");
+    assert_eq!(styled.unwrap(), "Part 2. x\nPart 3. y");
+
+    let styled = try_style("<ol marker='a)' start='0'><li>x</li><li>y</li></ol>");
+    assert_eq!(styled.unwrap(), "0) x\na) y");
+}
+
+#[test]
+pub fn it_aligns_wrapped_lines_under_the_text() {
+    let styled = style_at(
+        "<ol><li>the quick brown fox jumps</li><li>a<br/>b</li></ol>",
+        12,
+    );
+    assert_eq!(styled, "1. the quick\n   brown fox\n   jumps\n2. a\n   b");
+}
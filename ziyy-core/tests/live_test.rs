@@ -0,0 +1,67 @@
+use ziyy_core::LiveRegion;
+
+fn region(width: usize) -> LiveRegion {
+    let mut region = LiveRegion::new();
+    region.set_width(Some(width));
+    region
+}
+
+#[test]
+pub fn it_redraws_changed_lines_only() {
+    let mut region = region(10);
+    region.render_str("\x1b[1ma\nb\x1b[22m\nc");
+
+    assert_eq!(region.render_str("\x1b[1ma\nb\x1b[22m\nc"), "");
+    assert_eq!(
+        region.render_str("\x1b[1ma\nB\x1b[22m\nc"),
+        "\r\x1b[1A\x1b[2K\r\x1b[1mB\x1b[22m\r\x1b[1B\x1b[2G"
+    );
+}
+
+#[test]
+pub fn it_counts_wrapped_rows() {
+    let mut region = region(4);
+    region.render_str("abcdef\nx");
+
+    assert_eq!(region.render_str("abcdef\ny"), "\r\x1b[2K\ry");
+    assert_eq!(region.render_str("ab\ny"), "\r\x1b[2A\x1b[Jab\ny");
+    assert_eq!(region.render_str("ab\ny\nz"), "\r\nz");
+    assert_eq!(region.render_str("ab"), "\r\x1b[1A\x1b[J\r\x1b[1A\x1b[3G");
+    assert_eq!(region.render_str("cd\n"), "\r\x1b[2K\rcd\r\n");
+}
+
+#[test]
+pub fn it_redraws_everything_after_a_resize() {
+    let mut region = region(4);
+    region.render_str("abcdef\nx");
+
+    region.set_width(Some(2));
+    assert_eq!(region.render_str("abcdef\nx"), "\r\x1b[3A\x1b[Jabcdef\nx");
+    assert_eq!(region.clear(), "\r\x1b[3A\x1b[J");
+}
+
+#[test]
+pub fn it_keeps_only_the_styles_in_effect() {
+    let mut region = region(10);
+    let mut text: String = (0..50)
+        .map(|n| {
+            format!(
+                "\x1b[3{}m\x1b[1m\x1b]8;;u{n}\x1b\\x\x1b]8;;\x1b\\\x1b[22m\n",
+                n % 8
+            )
+        })
+        .collect();
+    region.render_str(&format!("{text}a"));
+    assert_eq!(
+        region.render_str(&format!("{text}b")),
+        "\r\x1b[2K\r\x1b[22;31mb"
+    );
+
+    text = "\x1b[4m\x1b]8;;u\x1b\\a\x1b[m\n".to_string();
+    region.reset();
+    region.render_str(&format!("{text}b"));
+    assert_eq!(
+        region.render_str(&format!("{text}c")),
+        "\r\x1b[2K\r\x1b]8;;u\x1b\\c"
+    );
+}
@@ -0,0 +1,82 @@
+use std::rc::Rc;
+
+use ziyy_core::{
+    Document, ErrorType, Indexer, Parser, Resolver, Result, Splitter, Tag, TagType, try_style,
+};
+
+fn tree(source: &str) -> Result<Rc<Document>> {
+    let source = Indexer::new().index(source).into_owned();
+    let frags = Splitter::new().split(&source)?;
+    let chunks = Parser::new(false).parse(frags)?;
+    Ok(Resolver::tree(chunks))
+}
+
+fn render(tree: Rc<Document>) -> String {
+    let doc = Resolver::new(false).resolve_tree(tree).unwrap();
+    let mut buf = String::new();
+    doc.root().to_string(&mut buf);
+    buf
+}
+
+#[test]
+pub fn it_appends_elements_and_text() {
+    let doc = tree("<p>a</p>").unwrap();
+    let p = doc.root().first_child().unwrap();
+
+    let b = p.try_append(Tag::open("b")).unwrap();
+    b.try_append_text("c").unwrap();
+
+    assert_eq!(render(doc), try_style("<p>a<b>c</b></p>").unwrap());
+}
+
+#[test]
+pub fn it_wraps_and_unwraps_ranges() {
+    let wrap = |doc: &Rc<Document>| {
+        let p = doc.root().first_child().unwrap();
+        let words: Vec<_> = p.children().collect();
+        words[0].wrap_range(&words[2], Tag::open("i")).unwrap()
+    };
+
+    let doc = tree("<p>a b c</p>").unwrap();
+    wrap(&doc).set_attribute("c", "red").unwrap();
+    assert_eq!(
+        render(doc),
+        try_style("<p><i c='red'>a b</i> c</p>").unwrap()
+    );
+
+    let doc = tree("<p>a b c</p>").unwrap();
+    let i = wrap(&doc);
+    i.unwrap_element().unwrap();
+    assert!(!i.is_live());
+    assert_eq!(render(doc), try_style("<p>a b c</p>").unwrap());
+}
+
+#[test]
+pub fn it_rejects_stale_and_invalid_nodes() {
+    let doc = tree("<p><b>a</b></p>").unwrap();
+    let p = doc.root().first_child().unwrap();
+    let b = p.first_child().unwrap();
+    let word = b.first_child().unwrap();
+
+    b.remove().unwrap();
+    let err = word.try_append_text("x").unwrap_err();
+    assert!(matches!(err.r#type, ErrorType::StaleNode));
+    let err = word.try_parent().unwrap_err();
+    assert!(matches!(err.r#type, ErrorType::StaleNode));
+    assert!(b.try_next_sibling().is_err());
+    assert!(p.try_first_child().unwrap().is_some());
+
+    // the removed ids are reused without reviving the stale nodes
+    p.try_append(Tag::open("i")).unwrap();
+    p.try_append(Tag::open("u")).unwrap();
+    assert!(!b.is_live() && !word.is_live());
+
+    let close = p.last_child().unwrap();
+    let err = close.try_insert_after(Tag::open("b")).unwrap_err();
+    assert!(matches!(err.r#type, ErrorType::InvalidOperation));
+
+    let mut tag = Tag::open("b");
+    tag.r#type = TagType::Close;
+    let err = p.try_append(tag).unwrap_err();
+    assert!(matches!(err.r#type, ErrorType::InvalidOperation));
+}
@@ -0,0 +1,49 @@
+use ziyy_core::{
+    Cache, ColorLevel, Doc, Icons, Live, RenderOptions, Streamer, try_style, try_style_for,
+};
+
+fn ascii(width: Option<usize>) -> RenderOptions {
+    RenderOptions {
+        level: ColorLevel::None,
+        width,
+        icons: Icons::Ascii,
+        ..RenderOptions::default()
+    }
+}
+
+#[test]
+pub fn it_styles_with_options() {
+    let source = "<p><b>:check:</b> one two three</p>";
+    assert_eq!(
+        try_style_for(source, ascii(Some(8))).unwrap(),
+        "[ok] one\ntwo\nthree"
+    );
+    assert_eq!(
+        try_style_for(source, RenderOptions::default()).unwrap(),
+        try_style(source).unwrap()
+    );
+
+    let mut cache = Cache::new(4);
+    cache.resolve(source, ascii(None)).unwrap();
+    cache.resolve(source, ascii(Some(8))).unwrap();
+    cache.resolve(source, ColorLevel::None).unwrap();
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+pub fn it_streams_with_options() {
+    let mut out = vec![];
+    Streamer::new(ascii(Some(8)))
+        .stream("<p>:x: one two three</p>\n".as_bytes(), &mut out)
+        .unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "[x] one\ntwo\nthree\n");
+}
+
+#[test]
+pub fn it_builds_and_lives_with_options() {
+    let doc = Doc::new().bold(":warning:").text(" low disk");
+    assert_eq!(doc.render_for(ascii(None)).unwrap(), "[!] low disk");
+
+    let mut live = Live::new(":check: {}", ascii(Some(20)));
+    assert_eq!(live.render(&[&"done"]).unwrap(), "[ok] done");
+}
@@ -0,0 +1,25 @@
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_keeps_whitespace_verbatim() {
+    let styled = try_style("x <pre>\n  a   b\n\n    <b>c</b>  </pre>");
+    assert_eq!(styled.unwrap(), "x \n  a   b\n\n    \x1b[1mc\x1b[22m  ");
+
+    let styled = try_style("<pre>\\<b\\> \\x41</pre>");
+    assert_eq!(styled.unwrap(), "<b> A");
+}
+
+#[test]
+pub fn it_expands_tabs() {
+    let styled = try_style("<pre tabsize='4'>\tx\ny\tz</pre>");
+    assert_eq!(styled.unwrap(), "    x\ny   z");
+}
+
+#[test]
+pub fn it_numbers_lines() {
+    let styled = try_style("<pre numbers='9'>\na\n\nb\n</pre>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[2m 9 │\x1b[22m a\n\x1b[2m10 │\x1b[22m \n\x1b[2m11 │\x1b[22m b\n"
+    );
+}
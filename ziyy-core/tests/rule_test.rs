@@ -0,0 +1,42 @@
+mod common;
+
+use common::style_at;
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_spans_the_width() {
+    assert_eq!(style_at("one<hr/>two", 5), "one\n─────\ntwo");
+    assert_eq!(style_at("<hr style='double'/>", 3), "═══");
+    assert_eq!(try_style("<hr char='=-' width='5'/>").unwrap(), "=-=-=");
+    assert_eq!(try_style("<hr/>").unwrap(), "─".repeat(80));
+}
+
+#[test]
+pub fn it_centers_labels() {
+    let styled = try_style("<hr width='16' label='Section' c='red'/>");
+    assert_eq!(styled.unwrap(), "\x1b[31m─── Section ────\x1b[39m");
+}
+
+#[test]
+pub fn it_inserts_spaces() {
+    let styled = try_style("a<space n='3'/>b<space/>c");
+    assert_eq!(styled.unwrap(), "a   b c");
+
+    let styled = style_at("<p>aaa<space n='4'/>bbb</p>", 6);
+    assert_eq!(styled, "aaa\nbbb");
+}
+
+#[test]
+pub fn it_fits_the_enclosing_block() {
+    let styled = style_at("<box>abc<hr/>def</box>", 10);
+    assert_eq!(
+        styled,
+        "┌────────┐\n│ abc    │\n│ ────── │\n│ def    │\n└────────┘"
+    );
+
+    let styled = style_at("<ul><li>abc<hr/>def</li></ul>", 6);
+    assert_eq!(styled, "• abc\n  ────\n  def");
+
+    let styled = style_at("<p indent='2'>abc<hr/>def</p>", 6);
+    assert_eq!(styled, "  abc\n  ────\n  def");
+}
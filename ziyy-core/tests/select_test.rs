@@ -0,0 +1,66 @@
+use ziyy_core::{ColorLevel, ErrorType, Selector, resolve};
+
+const SOURCE: &str = "<let id='w' c='red' />\
+    <table><tr><td class='b x'>a</td><td>b</td></tr></table>\
+    <p>see <a href='https://example.com' id='x'>here <b>now</b></a></p>";
+
+fn names(nodes: &[std::rc::Rc<ziyy_core::Node>]) -> Vec<String> {
+    nodes
+        .iter()
+        .map(|node| node.chunk().borrow().tag().unwrap().name().clone())
+        .collect()
+}
+
+#[test]
+pub fn it_selects_by_name_class_and_attribute() {
+    let doc = resolve(SOURCE, ColorLevel::TrueColor).unwrap();
+
+    let td = doc.select("table td.b").unwrap().unwrap();
+    assert_eq!(td.chunk().borrow().tag().unwrap().class(), "b x");
+    assert_eq!(doc.query_all("tr > td").unwrap().len(), 2);
+    assert_eq!(doc.query_all("td.b.x, td.y").unwrap().len(), 1);
+
+    let links = doc.query_all("a[href='https://example.com']").unwrap();
+    assert_eq!(names(&links), ["a"]);
+    assert!(doc.select("a[href=other]").unwrap().is_none());
+    assert_eq!(names(&doc.query_all("p > * > b").unwrap()), ["b"]);
+    assert!(doc.select("table > td").unwrap().is_none());
+}
+
+#[test]
+pub fn it_matches_attributes_by_name() {
+    let doc = resolve(SOURCE, ColorLevel::TrueColor).unwrap();
+
+    assert_eq!(names(&doc.query_all("a#x").unwrap()), ["a"]);
+    assert_eq!(names(&doc.query_all("a[id]").unwrap()), ["a"]);
+    assert!(doc.select("a#https://example.com").is_err());
+    assert!(doc.select("a[id='https://example.com']").unwrap().is_none());
+    assert!(doc.select("a[title]").unwrap().is_none());
+    assert!(doc.select("a[file]").unwrap().is_none());
+    assert_eq!(doc.query_all("td[class]").unwrap().len(), 1);
+}
+
+#[test]
+pub fn it_finds_the_closest_ancestor() {
+    let doc = resolve(SOURCE, ColorLevel::TrueColor).unwrap();
+    let b = doc.select("a b").unwrap().unwrap();
+
+    let p = b.closest("p").unwrap().unwrap();
+    assert_eq!(names(&[p]), ["p"]);
+    assert_eq!(b.closest("b").unwrap().unwrap().id(), b.id());
+    assert!(b.closest("table").unwrap().is_none());
+}
+
+#[test]
+pub fn it_rejects_invalid_selectors() {
+    for selector in ["", "td >", "td[", "td[href='x]", "p, ,b", ".", "a!"] {
+        let err = Selector::parse(selector).unwrap_err();
+        assert!(
+            matches!(
+                err.r#type,
+                ErrorType::InvalidSelector | ErrorType::UnterminatedString
+            ),
+            "{selector:?}: {err}"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use ziyy_core::{
+    Chunk, Fragment, FragmentType, Icons, RenderOptions, Span, WordParser, try_style, try_style_for,
+};
+
+fn render(source: &str, icons: Icons) -> String {
+    let options = RenderOptions {
+        icons,
+        ..RenderOptions::default()
+    };
+    try_style_for(source, options).unwrap()
+}
+
+#[test]
+pub fn it_expands_shortcodes() {
+    let styled = render(
+        "<b>:check:</b> built, :warning: 2 warnings :nf-fa-folder:",
+        Icons::Unicode,
+    );
+    assert_eq!(styled, "\x1b[1m✔\x1b[22m built, ⚠ 2 warnings \u{f07b}");
+
+    let styled = render("at 12:30:00 :nope: (:x:)", Icons::Unicode);
+    assert_eq!(styled, "at 12:30:00 :nope: (✖)");
+}
+
+#[test]
+pub fn it_leaves_text_as_is_by_default() {
+    let source = "done :check: root:x:0:0:root";
+    assert_eq!(try_style(source).unwrap(), source);
+
+    // shortcodes inside words are never expanded
+    assert_eq!(
+        render("root:x:0:0:root a:check:b :x:y", Icons::Unicode),
+        "root:x:0:0:root a:check:b :x:y"
+    );
+}
+
+#[test]
+pub fn it_falls_back_to_ascii() {
+    assert_eq!(
+        render(":check: passed, :x: failed", Icons::Ascii),
+        "[ok] passed, [x] failed"
+    );
+    assert_eq!(render(":check:", Icons::None), ":check:");
+
+    let mut parser = WordParser::new();
+    parser.set_icons(Icons::Ascii);
+    let source = ":warning:\x1b[1m:tada:";
+    let chunks: Vec<Chunk> = parser
+        .parse(Fragment::new(
+            FragmentType::Word,
+            source,
+            Span::calculate(source),
+        ))
+        .unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].word().unwrap(), "[!]");
+    assert_eq!(chunks[2].word().unwrap(), "\\o/");
+}
+
+#[test]
+pub fn it_keeps_shortcodes_in_code() {
+    assert_eq!(render("<pre>:check:</pre>", Icons::Unicode), ":check:");
+}
@@ -0,0 +1,19 @@
+use ziyy_core::{Splitter, try_style};
+
+fn lexemes(source: &str) -> Vec<&str> {
+    Splitter::new()
+        .split(source)
+        .unwrap()
+        .iter()
+        .map(|frag| frag.lexeme)
+        .collect()
+}
+
+#[test]
+pub fn it_keeps_escapes_inside_words() {
+    assert_eq!(lexemes("a\\<b>c"), ["a\\<b>c"]);
+    assert_eq!(try_style("a\\<b>c").unwrap(), "a<b>c");
+
+    assert_eq!(lexemes("\\ab\\<c d"), ["\\ab\\<c", " ", "d"]);
+    assert_eq!(lexemes("x\\\\<b>y</b>"), ["x\\\\", "<b>", "y", "</b>"]);
+}
@@ -0,0 +1,104 @@
+use ziyy_core::{ColorLevel, Streamer};
+
+fn stream(input: &str) -> std::io::Result<String> {
+    let mut out = vec![];
+    Streamer::new(ColorLevel::TrueColor).stream(input.as_bytes(), &mut out)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+pub fn it_renders_each_line() {
+    let out = stream("<b>a</b> b\nc <i>d</i>\n").unwrap();
+    assert_eq!(out, "\x1b[1ma\x1b[22m b\nc \x1b[3md\x1b[23m\n");
+}
+
+#[test]
+pub fn it_renders_element_once_closed() {
+    let mut streamer = Streamer::new(ColorLevel::TrueColor);
+    let mut out = vec![];
+
+    streamer.feed("<b>a\n", &mut out).unwrap();
+    assert!(out.is_empty());
+
+    streamer.feed("b</b>\n", &mut out).unwrap();
+    assert_eq!(out, b"\x1b[1ma b\x1b[22m\n");
+}
+
+#[test]
+pub fn it_keeps_bindings_and_blank_lines() {
+    let out = stream("<let id='w' c='red' />\n<w>a</w>\n\n<w>b</w>").unwrap();
+    assert_eq!(out, "\x1b[31ma\x1b[39m\n\n\x1b[31mb\x1b[39m");
+}
+
+#[test]
+pub fn it_ignores_brackets_in_strings_and_escapes() {
+    let out = stream("<b class='>'>a \\< b</b>\n").unwrap();
+    assert_eq!(out, "\x1b[1ma < b\x1b[22m\n");
+}
+
+#[test]
+pub fn it_writes_bad_input_as_text() {
+    assert_eq!(stream("a <b\n").unwrap(), "a <b\n");
+    assert_eq!(
+        stream("<b c='\n<i>x</i>'\n").unwrap(),
+        "<b c='\n<i>x</i>'\n"
+    );
+}
+
+#[test]
+pub fn it_writes_lone_brackets_as_text() {
+    let out = stream("x < y\n<b>ok</b>\n").unwrap();
+    assert_eq!(out, "x < y\n\x1b[1mok\x1b[22m\n");
+
+    let mut streamer = Streamer::new(ColorLevel::TrueColor);
+    let mut out = vec![];
+    streamer.feed("a <", &mut out).unwrap();
+    streamer.feed("b>c</b>\n", &mut out).unwrap();
+    assert_eq!(out, b"a \x1b[1mc\x1b[22m\n");
+
+    let mut out = vec![];
+    streamer.feed("<", &mut out).unwrap();
+    streamer.finish(&mut out).unwrap();
+    assert_eq!(out, b"<");
+}
+
+#[test]
+pub fn it_bounds_bindings() {
+    let mut streamer = Streamer::new(ColorLevel::TrueColor);
+    let mut out = vec![];
+    streamer.feed("<let id='w' c='red' />\n", &mut out).unwrap();
+    let binding = "<let id='v' c='blue' />\n";
+    // the newline is not part of the binding
+    for _ in 0..=Streamer::MAX_PENDING / (binding.len() - 1) {
+        streamer.feed(binding, &mut out).unwrap();
+    }
+    streamer.feed("<v>a</v><w>b</w>\n", &mut out).unwrap();
+    assert_eq!(out, b"\x1b[34ma\x1b[39mb\n");
+}
+
+#[test]
+pub fn it_keeps_indentation() {
+    let out = stream("  <b>a</b>\r\n\tb\n").unwrap();
+    assert_eq!(out, "  \x1b[1ma\x1b[22m\n\tb\n");
+}
+
+#[test]
+pub fn it_bounds_open_elements() {
+    let mut streamer = Streamer::new(ColorLevel::None);
+    let mut out = vec![];
+
+    let line = format!("{}\n", "a".repeat(1023));
+    let count = Streamer::MAX_PENDING / line.len();
+    streamer.feed("<b>", &mut out).unwrap();
+    for _ in 1..count {
+        streamer.feed(&line, &mut out).unwrap();
+    }
+    assert!(out.is_empty());
+
+    streamer.feed(&line, &mut out).unwrap();
+    assert_eq!(out.iter().filter(|&&c| c == b'a').count(), count * 1023);
+    assert!(out.ends_with(b"a\n"));
+
+    streamer.feed("b\n", &mut out).unwrap();
+    assert!(out.ends_with(b"a\nb\n"));
+}
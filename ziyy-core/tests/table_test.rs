@@ -0,0 +1,89 @@
+mod common;
+
+use common::{strip, style_at};
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_aligns_columns() {
+    let styled = try_style(
+        "<table align='left right center'>\
+            <tr><td>name</td><td>size</td><td>ok</td></tr>\
+            <tr><td>a</td><td>12345</td><td>yes</td></tr>\
+        </table>",
+    );
+    assert_eq!(
+        styled.unwrap(),
+        "name  size ok\n\
+         a    12345 yes"
+    );
+}
+
+#[test]
+pub fn it_draws_borders() {
+    let styled = try_style(
+        "<table border='single'>\
+            <tr><th>key</th><th>value</th></tr>\
+            <tr><td>a</td><td align='right'>1</td></tr>\
+            <tr><td colspan='2'>both</td></tr>\
+        </table>",
+    );
+    assert_eq!(
+        strip(&styled.unwrap()),
+        "┌─────┬───────┐\n\
+         │ key │ value │\n\
+         ├─────┼───────┤\n\
+         │ a   │     1 │\n\
+         │ both        │\n\
+         └─────────────┘"
+    );
+
+    let styled =
+        try_style("<table border='ascii' padding='0'><tr><td>a</td><td>b</td></tr></table>");
+    assert_eq!(styled.unwrap(), "+-+-+\n|a|b|\n+-+-+");
+}
+
+#[test]
+pub fn it_styles_header_cells() {
+    let styled = try_style("<table border='double'><tr><th>h</th></tr></table>");
+    assert_eq!(styled.unwrap(), "╔═══╗\n║ \x1b[1mh\x1b[22m ║\n╚═══╝");
+}
+
+#[test]
+pub fn it_wraps_cells_to_fit() {
+    let styled = style_at(
+        "<table border='rounded'>\
+            <tr><td>id</td><td><b>a long description</b></td></tr>\
+        </table>",
+        16,
+    );
+    assert_eq!(
+        strip(&styled),
+        "╭────┬─────────╮\n\
+         │ id │ a long  │\n\
+         │    │ descrip │\n\
+         │    │ tion    │\n\
+         ╰────┴─────────╯"
+    );
+    assert_eq!(styled.matches("\x1b[1m").count(), 3);
+}
+
+#[test]
+pub fn it_limits_cells_to_their_width() {
+    let styled = try_style("<table><tr><td width='3'>aaa bbb</td><td>c</td></tr></table>");
+    assert_eq!(styled.unwrap(), "aaa c\nbbb");
+}
+
+#[test]
+pub fn it_bounds_spans_and_skips_empty_rows() {
+    let styled = try_style("<table border='single'><tr></tr></table>|");
+    assert_eq!(styled.unwrap(), "|");
+
+    let styled = try_style(
+        "<table border='ascii' padding='0'>\
+            <tr></tr>\
+            <tr><td colspan='18446744073709551615'>a</td></tr>\
+            <tr><td>b</td><td>c</td></tr>\
+        </table>",
+    );
+    assert_eq!(styled.unwrap(), "+---+\n|a  |\n|b|c|\n+-+-+");
+}
@@ -0,0 +1,84 @@
+use ziyy_core::{ColorLevel, Indexer, Live, display_width, try_style};
+
+#[test]
+pub fn it_draws_progress_bars() {
+    let styled = try_style("<progress value='42' width='10' percent/>|");
+    assert_eq!(styled.unwrap(), "████▎       42%|");
+
+    let styled = try_style("<progress value='{0}' width='4'/>|");
+    assert_eq!(styled.unwrap(), "    |");
+
+    let styled = try_style("<progress value='1' max='1' width='3' from='#ff0000' to='#0000ff'/>");
+    assert_eq!(
+        styled.unwrap(),
+        "\x1b[38;2;255;0;0m█\x1b[38;2;128;0;128m█\x1b[38;2;0;0;255m█\x1b[39m"
+    );
+}
+
+#[test]
+pub fn it_draws_spinner_frames() {
+    let styled = try_style("<spinner frame='11'/><spinner style='line' frame='2'/>");
+    assert_eq!(styled.unwrap(), "⠙|");
+}
+
+#[test]
+pub fn it_fills_placeholders() {
+    let mut indexer = Indexer::new();
+    assert_eq!(
+        indexer.fill("{} {1} {{{0}}} {5}", &[&"a", &2]),
+        "a 2 {a} {5}"
+    );
+}
+
+#[test]
+pub fn it_fills_text_not_markup() {
+    let mut indexer = Indexer::new();
+    assert_eq!(
+        indexer.fill(
+            "<b>{}</b> <a href='{}'>{}</a>",
+            &[&"<i>", &"a'b<c>", &"C:\\x"]
+        ),
+        "<b>\\<i\\></b> <a href='ab<c>'>C:\\\\x</a>"
+    );
+
+    assert_eq!(
+        indexer.fill("<c c={}>x</c> <b {}>", &[&"red>\"<i", &"u"]),
+        "<c c=\"red><i\">x</c> <b \"u\">"
+    );
+    let styled = try_style(indexer.fill("<c c={}>x</c>", &[&"red><i"]));
+    assert_eq!(styled.unwrap(), "\x1b[31mx\x1b[39m");
+    let styled = try_style(indexer.fill("<b {}>x</b>", &[&"u"]));
+    assert_eq!(styled.unwrap(), "\x1b[1mx\x1b[22m");
+
+    let mut live = Live::new("{}: <progress value='{}' width='2'/>", ColorLevel::None);
+    assert_eq!(
+        live.render(&[&"<src/main.rs>", &100]).unwrap(),
+        "<src/main.rs>: ██"
+    );
+}
+
+#[test]
+pub fn it_renders_in_place() {
+    let mut live = Live::new("{}<br/><progress value='{}' width='4'/>", ColorLevel::None);
+    assert_eq!(live.render(&[&"a", &50]).unwrap(), "a\n██  ");
+    assert_eq!(
+        live.render(&[&"b", &100]).unwrap(),
+        "\r\x1b[1A\x1b[2K\rb\r\x1b[1B\x1b[2K\r████"
+    );
+
+    live.reset();
+    assert_eq!(live.render(&[&"c", &0]).unwrap(), "c\n    ");
+}
+
+#[test]
+pub fn it_limits_bar_widths() {
+    let styled = try_style("<progress value='5' width='18446744073709551615'/>").unwrap();
+    assert_eq!(display_width(&styled), 1000);
+
+    let styled = try_style("<bar value='5' width='18446744073709551615'/>").unwrap();
+    assert_eq!(display_width(&styled), 1000);
+
+    let mut live = Live::new("<progress value='100' width='30'/>", ColorLevel::None);
+    live.set_width(Some(5));
+    assert_eq!(live.render(&[]).unwrap(), "█████");
+}
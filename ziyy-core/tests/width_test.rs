@@ -0,0 +1,39 @@
+use ziyy_core::{display_width, graphemes, try_style};
+
+#[test]
+pub fn it_measures_display_width() {
+    assert_eq!(display_width("hello"), 5);
+    assert_eq!(display_width("日本語"), 6);
+    assert_eq!(display_width("ｆｕｌｌ"), 8);
+    assert_eq!(display_width("cafe\u{301}"), 4);
+    assert_eq!(display_width("✅ ok"), 5);
+    assert_eq!(display_width("⚠\u{FE0F}"), 2);
+    assert_eq!(display_width("👍🏽"), 2);
+    assert_eq!(display_width("👨‍👩‍👧"), 2);
+    assert_eq!(display_width("🇳🇬"), 2);
+    assert_eq!(display_width("a\u{200B}b"), 2);
+}
+
+#[test]
+pub fn it_splits_graphemes() {
+    let clusters: Vec<_> = graphemes("e\u{301}👨‍👩‍👧🇳🇬x").collect();
+    assert_eq!(clusters, ["e\u{301}", "👨‍👩‍👧", "🇳🇬", "x"]);
+}
+
+#[test]
+pub fn it_aligns_table_columns_by_display_width() {
+    let styled = try_style(
+        "<table>\
+            <tr><td>名前</td><td>✅</td><td>x</td></tr>\
+            <tr><td>name</td><td>ok</td><td>y</td></tr>\
+            <tr><td>naïve</td><td>👍🏽</td><td>z</td></tr>\
+        </table>",
+    )
+    .unwrap();
+
+    let columns: Vec<_> = styled
+        .lines()
+        .map(|line| display_width(&line[..line.rfind(' ').unwrap()]))
+        .collect();
+    assert_eq!(columns, [8, 8, 8]);
+}
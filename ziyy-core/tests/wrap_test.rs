@@ -0,0 +1,71 @@
+mod common;
+
+use common::{strip, style_at};
+use ziyy_core::try_style;
+
+#[test]
+pub fn it_wraps_at_the_width_attribute() {
+    let styled = try_style("<p width='16'>the quick brown fox jumps over the lazy dog</p>");
+    assert_eq!(
+        styled.unwrap(),
+        "the quick brown\nfox jumps over\nthe lazy dog"
+    );
+}
+
+#[test]
+pub fn it_keeps_styles_across_line_breaks() {
+    let styled = try_style("<p width='12'>one <b>two three four</b> five</p>");
+    assert_eq!(styled.unwrap(), "one \x1b[1mtwo\nthree four\x1b[22m\nfive");
+}
+
+#[test]
+pub fn it_indents_wrapped_lines() {
+    let styled = try_style("<p indent='2' hang='2' width='14'>aaa bbb ccc ddd eee fff<br/>ggg</p>");
+    assert_eq!(
+        styled.unwrap(),
+        "  aaa bbb ccc\n    ddd eee\n    fff\n    ggg"
+    );
+}
+
+#[test]
+pub fn it_wraps_to_the_resolver_width() {
+    let styled = style_at("<div>aaa bbb ccc ddd</div>", 8);
+    assert_eq!(styled, "aaa bbb\nccc ddd");
+
+    let styled = style_at("<div width='4'>aaa bbb</div>", 8);
+    assert_eq!(styled, "aaa\nbbb");
+
+    let styled = style_at("aaa bbb ccc ddd", 8);
+    assert_eq!(styled, "aaa bbb ccc ddd");
+}
+
+#[test]
+pub fn it_aligns_wrapped_cells() {
+    let styled = style_at(
+        "<table><tr><td>-c</td><td>read from the cli</td></tr></table>",
+        14,
+    );
+    assert_eq!(styled, "-c read from\n   the cli");
+}
+
+#[test]
+pub fn it_leaves_pre_untouched() {
+    let styled = style_at("<div>aa <pre>bb  cc dd ee</pre></div>", 6);
+    assert_eq!(styled, "aa \nbb  cc dd ee");
+}
+
+#[test]
+pub fn it_leaves_no_space_before_line_breaks() {
+    for source in [
+        "<p>aa <u>bb cc dd</u> ee</p>",
+        "<p><b>aaaa </b>bbbb <i>cc</i>  dd</p>",
+        "<ul><li>aaa bbb\n    ccc ddd</li></ul>",
+        "<table><tr><td>x</td><td>aa <b>bb</b> cc dd</td></tr></table>",
+    ] {
+        let styled = style_at(source, 6);
+        assert!(styled.lines().count() > 1, "{source:?}");
+        for line in strip(&styled).lines() {
+            assert!(!line.ends_with(' '), "{source:?}: {line:?}");
+        }
+    }
+}
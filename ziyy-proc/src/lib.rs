@@ -3,6 +3,7 @@ use quote::quote_spanned;
 use syn::parse::{End, Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::{Expr, LitStr, Token, parse_macro_input};
+use ziyy_core::ColorLevel;
 
 struct StyleFmt {
     source: LitStr,
@@ -65,3 +66,33 @@ pub fn style(tokens: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Styles text for every color level.
+///
+/// Expands to a `ziyy_core::StyleSet` holding the truecolor, 256 color,
+/// 16 color and plain variants, so it can initialize a `const` or `static`.
+#[proc_macro]
+pub fn style_set(tokens: TokenStream) -> TokenStream {
+    let source = parse_macro_input!(tokens as LitStr);
+    let span = source.span();
+    let style = |level| match ziyy_core::try_style_for(source.value(), level) {
+        Ok(s) => s,
+        Err(e) => panic!("{e}"),
+    };
+
+    let truecolor = style(ColorLevel::TrueColor);
+    let ansi256 = style(ColorLevel::Ansi256);
+    let ansi16 = style(ColorLevel::Ansi16);
+    let plain = style(ColorLevel::None);
+
+    let expanded = quote_spanned! {
+        span => ::ziyy_core::StyleSet {
+            truecolor: #truecolor,
+            ansi256: #ansi256,
+            ansi16: #ansi16,
+            plain: #plain,
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,32 @@
+use ziyy_core::{ColorLevel, StyleSet};
+use ziyy_proc::style_set;
+
+const HELLO: StyleSet =
+    style_set!("<b c='rgb(255, 0, 0)'>Hello</b> <x rgb='128, 128, 128'>world</x>");
+
+#[test]
+pub fn it_expands_into_a_const() {
+    assert_eq!(
+        HELLO.truecolor,
+        "\x1b[1;38;2;255;0;0mHello\x1b[22;39m \x1b[48;2;128;128;128mworld\x1b[49m"
+    );
+    assert_eq!(
+        HELLO.ansi256,
+        "\x1b[1;38;5;196mHello\x1b[22;39m \x1b[48;5;243mworld\x1b[49m"
+    );
+    assert_eq!(
+        HELLO.ansi16,
+        "\x1b[1;91mHello\x1b[22;39m \x1b[100mworld\x1b[49m"
+    );
+    assert_eq!(HELLO.plain, "Hello world");
+}
+
+#[test]
+pub fn it_picks_the_variant_for_a_level() {
+    const PLAIN: &str = HELLO.get(ColorLevel::None);
+
+    assert_eq!(PLAIN, HELLO.plain);
+    assert_eq!(HELLO.get(ColorLevel::Ansi16), HELLO.ansi16);
+    assert_eq!(HELLO.get(ColorLevel::Ansi256), HELLO.ansi256);
+    assert_eq!(HELLO.get(ColorLevel::TrueColor), HELLO.truecolor);
+}